@@ -1,23 +1,28 @@
 /*!
-This module provides the configuration setup and command-line argument parsing for a server application. It utilizes the `clap` crate for parsing command-line arguments and the `config` crate for managing configuration through a combination of command-line arguments, environment variables, and default values.
+This module provides the configuration setup and command-line argument parsing for a server application. It utilizes the `clap` crate for parsing command-line arguments and the `config` crate for managing configuration through a combination of command-line arguments, environment variables, an optional configuration file, and default values.
 
 # Dependencies
 - `clap::{Parser, ValueEnum}`
 - `serde::Deserialize`
 - `std::net::Ipv4Addr`
-- `config::{Config, Environment, ConfigError}`
+- `config::{Config, Environment, File, ConfigError}`
 
 # Components
 1. `LogMethod`: An enum representing the different logging methods.
 2. `ServerConfig`: A struct representing the server configuration.
-3. `Args`: A struct representing the command-line arguments.
-4. `parse_args`: A function to parse command-line arguments and merge them with other configuration sources.
+3. `Command`: An enum representing the commands that run instead of the server.
+4. `Args`: A struct representing the command-line arguments.
+5. `parse_args`: A function to parse command-line arguments and merge them with other configuration sources.
 */
-use clap::{Parser, ValueEnum};
-use config::{Config, ConfigError, Environment};
-use serde::Deserialize;
+use crate::workhours::{holidays::Country, PeriodFormat, UbwRules, WorkSchedule};
+use crate::DEFAULT_MAX_BATCH_SIZE;
+use clap::{Parser, Subcommand, ValueEnum};
+use config::{Config, ConfigError, Environment, File};
 use core::fmt;
+use serde::Deserialize;
+use std::collections::HashMap;
 use std::net::Ipv4Addr;
+use std::path::PathBuf;
 
 /// Defines the logging methods available for the server.
 #[derive(ValueEnum, Clone, Debug, Deserialize)]
@@ -68,11 +73,64 @@ pub struct ServerConfig {
     pub subscriber: LogMethod,
     /// Log level verbosity
     pub verbose: u8,
+    /// Named work schedules that requests can refer to, e.g. `parttime = "8,8,8,8,0,0,0"`
+    #[serde(default)]
+    pub schedules: HashMap<String, WorkSchedule>,
+    /// Rules of the UBW period scheme for short weeks at month breaks, set in a `[ubw]` table
+    #[serde(default)]
+    pub ubw: UbwRules,
+    /// The largest number of items allowed in a batch request
+    pub max_batch_size: usize,
+    /// A command to run instead of the server
+    #[serde(skip)]
+    pub command: Option<Command>,
+}
+
+/// Commands that print their output and exit instead of running the server
+#[derive(Subcommand, Clone, Debug)]
+pub enum Command {
+    /// Print all reporting periods of a year with their dates, working days and hours
+    Periods {
+        /// The year to list the periods of
+        year: i32,
+
+        /// Output format: json, csv or ics
+        #[arg(short, long, default_value = "json")]
+        format: PeriodFormat,
+
+        /// Country whose holidays to exclude: SE, NO, DK or FI
+        #[arg(long, default_value = "SE")]
+        country: Country,
+
+        /// A named work schedule or hours per weekday, e.g. "8,8,8,8,6,0,0"
+        #[arg(long)]
+        schedule: Option<String>,
+
+        /// Period scheme: ubw, isoweek, month, biweekly or fiscal445
+        #[arg(long, default_value = "ubw")]
+        scheme: String,
+
+        /// The first day of any bi-weekly period (DD-MM-YYYY, YYYY-MM-DD or YYYYMMDD), required
+        /// for biweekly
+        #[arg(long)]
+        anchor: Option<String>,
+
+        /// The ISO week the fiscal years of fiscal445 start in
+        #[arg(long)]
+        fiscal_start_week: Option<u32>,
+    },
 }
 /// Command-line arguments structure
 #[derive(Parser, Debug, Deserialize)]
 #[command(version, about, long_about= None)]
 struct Args {
+    /// Path to a configuration file
+    ///
+    /// The file may set any of the options below, as well as named work
+    /// schedules in a `[schedules]` table and UBW period rules in a `[ubw]` table.
+    #[arg(short, long)]
+    pub config: Option<PathBuf>,
+
     /// Port number for the API server
     ///
     /// Must be between 1 and 65535
@@ -105,6 +163,13 @@ struct Args {
     #[arg(short, long, value_enum)]
     pub subscriber: Option<LogMethod>,
 
+    /// The largest number of items allowed in a batch request
+    #[arg(long, long_help = format!(
+        "The largest number of items allowed in a batch request\n\nDefault is {}",
+        DEFAULT_MAX_BATCH_SIZE
+    ))]
+    pub max_batch_size: Option<usize>,
+
     ///log level
     ///
     /// Default is INFO (3), equivalent to "-verbose -verbose -verbose" or "-vvv"
@@ -116,9 +181,14 @@ struct Args {
     ///  - Trace (5): -vvvvv
     #[arg(short, long, action = clap::ArgAction::Count)]
     verbose: u8,
+
+    /// Command to run instead of the server
+    #[command(subcommand)]
+    #[serde(skip)]
+    command: Option<Command>,
 }
 
-/// Parses command-line arguments and merges them with configuration from environment variables and defaults.
+/// Parses command-line arguments and merges them with configuration from an optional configuration file, environment variables and defaults.
 pub fn parse_args() -> Result<ServerConfig, ConfigError> {
     let cli_args = Args::parse();
 
@@ -130,6 +200,13 @@ pub fn parse_args() -> Result<ServerConfig, ConfigError> {
         .set_default("metrics_port", 3201)?
         .set_default("subscriber", LogMethod::Stdout.to_string())?
         .set_default("verbose", 3)?
+        .set_default("max_batch_size", DEFAULT_MAX_BATCH_SIZE as u64)?;
+
+    if let Some(path) = cli_args.config.as_deref() {
+        config_builder = config_builder.add_source(File::from(path));
+    }
+
+    config_builder = config_builder
         .add_source(Environment::with_prefix("TIMETALLY"))
         .set_override_option("api_network", cli_args.api_network.map(|v| v.to_string()))?
         .set_override_option("api_port", cli_args.api_port.map(|v| v.to_string()))?
@@ -139,11 +216,22 @@ pub fn parse_args() -> Result<ServerConfig, ConfigError> {
             cli_args.metrics_network.map(|v| v.to_string()),
         )?
         .set_override_option("metrics_port", cli_args.metrics_port.map(|v| v.to_string()))?
-        .set_override_option("subscriber", cli_args.subscriber.map(|v| v.to_string()))?;
+        .set_override_option("subscriber", cli_args.subscriber.map(|v| v.to_string()))?
+        .set_override_option(
+            "max_batch_size",
+            cli_args.max_batch_size.map(|v| v.to_string()),
+        )?;
 
     if cli_args.verbose > 0 {
         config_builder = config_builder.set_override("verbose", cli_args.verbose.to_string())?;
     }
-    let config = config_builder.build()?.try_deserialize::<ServerConfig>()?;
+    let mut config = config_builder.build()?.try_deserialize::<ServerConfig>()?;
+    config.ubw.validate().map_err(ConfigError::Message)?;
+    if config.max_batch_size == 0 {
+        return Err(ConfigError::Message(
+            "max_batch_size must be at least 1".to_string(),
+        ));
+    }
+    config.command = cli_args.command;
     Ok(config)
 }
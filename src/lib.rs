@@ -9,11 +9,22 @@
 //! - `run_api_server`: Sets up and runs the main API server.
 //! - `run_metrics_server`: Sets up and runs a separate metrics server.
 //! - `get_workhours`: Handles requests to calculate work hours.
+//! - `post_workhours_batch`: Handles requests to calculate work hours for many ranges at once.
+//! - `post_reconcile`: Handles requests to compare reported hours against expected hours.
+//! - `get_workhours_v2`: Handles requests to calculate work hours in the structured v2 format.
+//! - `get_period`: Handles requests to look up the reporting period of a date.
+//! - `get_periods`: Handles requests to list all reporting periods of a year.
+//! - `get_flex`: Handles requests to calculate a flex-time balance.
+//! - `get_overtime`: Handles requests to classify overtime into simple and qualified overtime.
+//! - `get_vacation`: Handles requests to calculate the vacation days of a vacation year.
+//! - `get_holidays`: Handles requests to list holidays.
+//! - `run_command`: Runs a command-line command instead of the server.
 //!
 //!  # Examples
 //! ```no_run
 //! use time_tally::args::parse_args;
-//! use time_tally::{run_api_server, run_metrics_server, setup_tracing_subscriber};
+//! use time_tally::workhours::Clock;
+//! use time_tally::{run_api_server, run_metrics_server, setup_tracing_subscriber, AppState};
 //! use tokio::signal;
 //!
 //! #[tokio::main]
@@ -22,7 +33,13 @@
 //!
 //!     setup_tracing_subscriber(args.subscriber, args.verbose);
 //!
-//!     run_api_server(args.api_network.to_string(), args.api_port.to_string()).await;
+//!     let state = AppState {
+//!         schedules: args.schedules,
+//!         ubw_rules: args.ubw,
+//!         clock: Clock::System,
+//!         max_batch_size: args.max_batch_size,
+//!     };
+//!     run_api_server(args.api_network.to_string(), args.api_port.to_string(), state).await;
 //!
 //!     if args.metrics {
 //!         run_metrics_server(
@@ -35,21 +52,190 @@
 //! }
 //! ```
 pub mod args;
+mod problem;
 pub mod workhours;
 
 use axum::{
-    extract::Query,
-    http::{self, StatusCode},
+    extract::{rejection::JsonRejection, Query, State},
+    http::{self, header},
     response::{IntoResponse, Json},
-    routing::get,
+    routing::{get, post},
     Router,
 };
 
-use serde::Deserialize;
+use chrono::NaiveDate;
+pub use problem::ApiError;
+use problem::Problem;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 use tokio::task;
 use tower_http::trace::TraceLayer;
 use tracing::info;
-use workhours::calculate_workhours;
+use workhours::{
+    calculate_flex, calculate_overtime, calculate_report, calculate_workhours,
+    calculate_workhours_detailed,
+    holidays::{Country, HolidayCalendar, HolidayKind, Language, SwedishCalendar},
+    list_holidays, lookup_period, month_range, period_calendar, period_range, reconcile_hours,
+    resolve_relative_date, vacation_balance, year_range, CalculationOptions, Clock, DateBound,
+    DateInputError, EmploymentRate, FlexRules, Hours, PeriodFormat, PeriodScheme, Rounding,
+    SchedulePlan, UbwRules, VacationRules, WorkHours, WorkSchedule,
+};
+
+/// The largest number of items allowed in a batch request unless configured otherwise.
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 1000;
+
+/// Shared state available to all API handlers.
+#[derive(Debug)]
+pub struct AppState {
+    /// Named work schedules that requests can refer to with `schedule=<name>`
+    pub schedules: HashMap<String, WorkSchedule>,
+    /// The rules of the UBW period scheme for short weeks at month breaks
+    pub ubw_rules: UbwRules,
+    /// The clock that relative dates such as "today" are resolved against
+    pub clock: Clock,
+    /// The largest number of items allowed in a batch request
+    pub max_batch_size: usize,
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        AppState {
+            schedules: HashMap::new(),
+            ubw_rules: UbwRules::default(),
+            clock: Clock::default(),
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+        }
+    }
+}
+
+impl AppState {
+    /// Resolves the schedule parameter of a request.
+    ///
+    /// # Arguments
+    ///
+    /// * `schedule` - Either the name of a configured schedule or a schedule such as
+    ///   "8,8,8,8,6,0,0". Defaults to 8 hours Monday to Friday.
+    ///
+    /// # Returns
+    ///
+    /// Returns the work schedule, or an error message if it is neither a named schedule
+    /// nor a valid schedule.
+    fn work_schedule(&self, schedule: Option<&str>) -> Result<WorkSchedule, String> {
+        match schedule {
+            None => Ok(WorkSchedule::default()),
+            Some(schedule) => match self.schedules.get(schedule) {
+                Some(named) => Ok(*named),
+                None if !schedule.contains(',') => Err(format!("Unknown schedule '{}'", schedule)),
+                None => schedule.parse(),
+            },
+        }
+    }
+
+    /// Resolves the schedule and schedule overrides parameters of a request.
+    ///
+    /// # Arguments
+    ///
+    /// * `schedule` - The default schedule, resolved as by `work_schedule`.
+    /// * `overrides` - Semicolon-separated date ranges with the schedule to use within
+    ///   them, e.g. "24-06-2024..15-08-2024:summer;23-12-2024..27-12-2024:0,0,0,0,0,0,0".
+    ///   Later ranges take precedence over earlier ones.
+    ///
+    /// # Returns
+    ///
+    /// Returns the schedule plan, or an error naming the `schedule` or `overrides` parameter
+    /// if any schedule or date range is invalid.
+    fn schedule_plan(
+        &self,
+        schedule: Option<&str>,
+        overrides: Option<&str>,
+    ) -> Result<SchedulePlan, ApiError> {
+        let schedule = self
+            .work_schedule(schedule)
+            .map_err(|err| ApiError::invalid("schedule", err))?;
+        let mut plan = SchedulePlan::new(schedule);
+        for segment in overrides
+            .into_iter()
+            .flat_map(|overrides| overrides.split(';'))
+        {
+            let invalid = || {
+                ApiError::invalid(
+                    "overrides",
+                    format!(
+                        "Invalid schedule override '{}': expected DATE..DATE:SCHEDULE",
+                        segment
+                    ),
+                )
+            };
+            let (range, schedule) = segment.split_once(':').ok_or_else(invalid)?;
+            let (from, to) = range.split_once("..").ok_or_else(invalid)?;
+            let parse_date =
+                |date: &str| workhours::parse_single_day(date.trim()).map_err(|_| invalid());
+            let schedule = self
+                .work_schedule(Some(schedule.trim()))
+                .map_err(|err| ApiError::invalid("overrides", err))?;
+            plan = plan
+                .with_segment(parse_date(from)?, parse_date(to)?, schedule)
+                .map_err(|err| ApiError::invalid("overrides", err))?;
+        }
+        Ok(plan)
+    }
+
+    /// Builds the calculation options of a request.
+    ///
+    /// # Returns
+    ///
+    /// Returns the options, or an error naming the parameter if the calendar, schedule,
+    /// employment rate, period scheme, as of or absences parameters are invalid.
+    fn calculation_options(
+        &self,
+        params: &CalculationParams,
+    ) -> Result<CalculationOptions, ApiError> {
+        let scheme = period_scheme(
+            params.scheme.as_deref(),
+            params.anchor.as_deref(),
+            params.fiscal_start_week,
+        )?
+        .with_ubw_rules(self.ubw_rules);
+        let as_of = params
+            .as_of
+            .as_deref()
+            .map(|as_of| {
+                let relative =
+                    resolve_relative_date(as_of, DateBound::Start, self.clock.today(), &scheme)?;
+                match relative {
+                    Some(date) => Ok(date),
+                    None => workhours::parse_date(as_of, DateBound::Start)
+                        .map_err(|err| ApiError::invalid("as_of", err.to_string())),
+                }
+            })
+            .transpose()?;
+        Ok(CalculationOptions {
+            calendar: holiday_calendar(params.country, params.eve_hours)?,
+            schedule: self
+                .schedule_plan(params.schedule.as_deref(), params.overrides.as_deref())?,
+            employment_rate: params
+                .rate
+                .map(EmploymentRate::new)
+                .transpose()
+                .map_err(|err| ApiError::invalid("rate", err))?
+                .unwrap_or_default(),
+            rounding: params.rounding,
+            scheme,
+            as_of,
+            absences: params
+                .absences
+                .iter()
+                .flat_map(|absences| absences.split(';'))
+                .filter(|absence| !absence.trim().is_empty())
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .map_err(|err| ApiError::invalid("absences", err))?,
+        })
+    }
+}
 
 /// Sets up the tracing subscriber based on the specified logging method and verbosity level.
 ///
@@ -136,6 +322,7 @@ fn get_log_level(verbose: u8) -> tracing::Level {
 ///
 /// * `network` - The network address to bind to.
 /// * `port` - The port number to listen on.
+/// * `state` - The state shared by all handlers, such as named work schedules.
 ///
 /// # Panics
 ///
@@ -144,14 +331,14 @@ fn get_log_level(verbose: u8) -> tracing::Level {
 /// # Examples
 ///
 /// ```no_run
-/// use time_tally::run_api_server;
+/// use time_tally::{run_api_server, AppState};
 ///
 /// #[tokio::main]
 /// async fn main() {
-///     run_api_server("127.0.0.1".to_string(), "3000".to_string()).await;
+///     run_api_server("127.0.0.1".to_string(), "3000".to_string(), AppState::default()).await;
 /// }
 /// ```
-pub async fn run_api_server(network: String, port: String) {
+pub async fn run_api_server(network: String, port: String, state: AppState) {
     let trace_layer = TraceLayer::new_for_http()
         .on_request(|request: &http::Request<_>, _span: &tracing::Span| {
             info!("started {} {}", request.method(), request.uri());
@@ -168,7 +355,17 @@ pub async fn run_api_server(network: String, port: String) {
 
     let router = Router::new()
         .route("/api/v1/workhours", get(get_workhours))
-        .layer(trace_layer);
+        .route("/api/v1/workhours/batch", post(post_workhours_batch))
+        .route("/api/v1/reconcile", post(post_reconcile))
+        .route("/api/v1/holidays", get(get_holidays))
+        .route("/api/v1/period", get(get_period))
+        .route("/api/v1/periods", get(get_periods))
+        .route("/api/v1/vacation", get(get_vacation))
+        .route("/api/v1/flex", get(get_flex))
+        .route("/api/v1/overtime", get(get_overtime))
+        .route("/api/v2/workhours", get(get_workhours_v2))
+        .layer(trace_layer)
+        .with_state(Arc::new(state));
     // TODO: Other good layers to include?
 
     let listener = tokio::net::TcpListener::bind(format!("{}:{}", network, port))
@@ -221,42 +418,863 @@ async fn get_metrics() -> &'static str {
     "hello world"
 }
 
+/// Runs a command-line command instead of the server.
+///
+/// # Arguments
+///
+/// * `command` - The command to run.
+/// * `state` - The state holding the named work schedules.
+///
+/// # Returns
+///
+/// Returns the output of the command, or an error message.
+///
+/// # Examples
+///
+/// ```
+/// use time_tally::{args::Command, run_command, AppState};
+///
+/// let command = Command::Periods {
+///     year: 2025,
+///     format: "csv".parse().unwrap(),
+///     country: "SE".parse().unwrap(),
+///     schedule: None,
+///     scheme: "ubw".to_string(),
+///     anchor: None,
+///     fiscal_start_week: None,
+/// };
+/// let csv = run_command(command, &AppState::default()).unwrap();
+/// assert!(csv.starts_with("number,id,name,start,end,working_days,hours"));
+/// ```
+pub fn run_command(command: args::Command, state: &AppState) -> Result<String, String> {
+    match command {
+        args::Command::Periods {
+            year,
+            format,
+            country,
+            schedule,
+            scheme,
+            anchor,
+            fiscal_start_week,
+        } => {
+            let scheme = period_scheme(Some(&scheme), anchor.as_deref(), fiscal_start_week)
+                .map_err(|err| err.to_string())?;
+            let options = CalculationOptions {
+                calendar: country.calendar(),
+                schedule: state.work_schedule(schedule.as_deref())?.into(),
+                scheme: scheme.with_ubw_rules(state.ubw_rules),
+                ..Default::default()
+            };
+            let calendar = period_calendar(year, &options).map_err(|err| err.to_string())?;
+            Ok(calendar.render(format))
+        }
+    }
+}
+
 /// Handles requests to get work hours.
 ///
-/// Calculates work hours based on the provided start and end dates.
+/// Calculates work hours based on the provided start and end dates, using the
+/// holiday calendar of the requested country (Sweden by default) and the requested
+/// work schedule (8 hours Monday to Friday by default).
 ///
 /// # Arguments
 ///
-/// * `Query(query)` - Query parameters containing start and end dates.
+/// * `State(state)` - The shared state holding the named work schedules.
+/// * `Query(query)` - Query parameters containing start and end dates and whether to include
+///   period details.
+/// * `Query(params)` - Query parameters containing an optional country, optional hours for
+///   reduced eves, an optional work schedule with date-ranged overrides and an optional
+///   employment rate with its rounding.
 ///
 /// # Returns
 ///
-/// Returns a JSON response with the calculated work hours or an error message.
-async fn get_workhours(Query(query): Query<QueryParams>) -> impl IntoResponse {
-    let result = task::spawn_blocking(move || calculate_workhours(query.start, query.end)).await;
+/// Returns a JSON response with the calculated work hours, or a problem+json error naming
+/// the invalid parameter.
+async fn get_workhours(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<QueryParams>,
+    Query(params): Query<CalculationParams>,
+) -> impl IntoResponse {
+    let result = task::spawn_blocking(move || query.workhours(&params, &state)).await;
 
     match result {
         Ok(Ok(workhours)) => Json(workhours).into_response(),
-        Ok(Err(err)) => (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({ "error": err })),
+        Ok(Err(err)) => err.into_response(),
+        Err(_) => ApiError::Internal.into_response(),
+    }
+}
+
+/// Handles requests to calculate work hours for many ranges at once.
+///
+/// Each item of the JSON array body takes the same fields as the query parameters of
+/// `get_workhours`, plus an optional `id` that is echoed back. Items are calculated one after
+/// another in a single task on the blocking pool and their results are returned in the order
+/// of the request, each either as a `result` or as an `error` with the problem details of
+/// that item.
+///
+/// # Arguments
+///
+/// * `State(state)` - The shared state holding the named work schedules and the largest
+///   number of items allowed.
+/// * `payload` - The JSON array of items, or the reason it could not be read.
+///
+/// # Returns
+///
+/// Returns a JSON array with the outcome of each item, or a problem+json error if the body
+/// is invalid or has more items than allowed.
+async fn post_workhours_batch(
+    State(state): State<Arc<AppState>>,
+    payload: Result<Json<Vec<BatchItem>>, JsonRejection>,
+) -> impl IntoResponse {
+    let items = match payload {
+        Ok(Json(items)) => items,
+        Err(rejection) => return ApiError::InvalidBody(rejection.body_text()).into_response(),
+    };
+    if items.len() > state.max_batch_size {
+        return ApiError::BatchTooLarge {
+            size: items.len(),
+            max: state.max_batch_size,
+        }
+        .into_response();
+    }
+
+    //One blocking task for the whole batch, so that a large batch can't take over the pool
+    let result = task::spawn_blocking(move || {
+        items
+            .into_iter()
+            .map(|item| BatchResult {
+                outcome: match item.range.workhours(&item.params, &state) {
+                    Ok(workhours) => BatchOutcome::Result(workhours),
+                    Err(err) => BatchOutcome::Error(err.problem()),
+                },
+                id: item.id,
+            })
+            .collect::<Vec<_>>()
+    })
+    .await;
+
+    match result {
+        Ok(results) => Json(results).into_response(),
+        Err(_) => ApiError::Internal.into_response(),
+    }
+}
+
+/// An item of a batch request.
+#[derive(Deserialize)]
+struct BatchItem {
+    /// An optional identifier of the item, echoed back with its result.
+    id: Option<serde_json::Value>,
+    /// The range of the item, as for `get_workhours`.
+    #[serde(flatten)]
+    range: QueryParams,
+    /// How the work hours of the item are calculated, as for `get_workhours`.
+    #[serde(flatten)]
+    params: CalculationParams,
+}
+
+/// The outcome of an item of a batch request.
+#[derive(Serialize)]
+struct BatchResult {
+    /// The identifier of the item, if it had one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<serde_json::Value>,
+    #[serde(flatten)]
+    outcome: BatchOutcome,
+}
+
+/// The work hours of a batch item, or the problem that prevented calculating them.
+#[derive(Serialize)]
+enum BatchOutcome {
+    #[serde(rename = "result")]
+    Result(WorkHours),
+    #[serde(rename = "error")]
+    Error(Problem),
+}
+
+/// Handles requests to compare reported hours against the expected hours of each period.
+///
+/// The JSON body takes the same fields as the query parameters of `get_workhours`, except
+/// `detail`, plus the `reported` hours keyed by period id or by date.
+///
+/// # Arguments
+///
+/// * `State(state)` - The shared state holding the named work schedules.
+/// * `payload` - The JSON body, or the reason it could not be read.
+///
+/// # Returns
+///
+/// Returns a JSON response with the expected hours, reported hours and their difference for
+/// each period and the whole range, flagging under- and over-reporting, or a problem+json
+/// error naming the invalid field.
+async fn post_reconcile(
+    State(state): State<Arc<AppState>>,
+    payload: Result<Json<ReconcileRequest>, JsonRejection>,
+) -> impl IntoResponse {
+    let request = match payload {
+        Ok(Json(request)) => request,
+        Err(rejection) => return ApiError::InvalidBody(rejection.body_text()).into_response(),
+    };
+    let result = task::spawn_blocking(move || -> Result<_, ApiError> {
+        let options = state.calculation_options(&request.params)?;
+        let (start, end) = request.range.range(&options, state.clock.today())?;
+        Ok(reconcile_hours(start, end, &request.reported, &options)?)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(reconciliation)) => Json(reconciliation).into_response(),
+        Ok(Err(err)) => err.into_response(),
+        Err(_) => ApiError::Internal.into_response(),
+    }
+}
+
+/// The body of a reconciliation request.
+#[derive(Deserialize)]
+struct ReconcileRequest {
+    /// The range to reconcile, as for `get_workhours`.
+    #[serde(flatten)]
+    range: QueryParams,
+    /// How the expected hours are calculated, as for `get_workhours`.
+    #[serde(flatten)]
+    params: CalculationParams,
+    /// The reported hours by period id, e.g. "2024-06-W23", or by date, e.g. "2024-06-03".
+    reported: BTreeMap<String, Hours>,
+}
+
+/// Handles requests to get work hours in the structured v2 format.
+///
+/// Takes the same query parameters as `get_workhours`, except `detail`, and returns
+/// ordered arrays of years, months and periods with the details of every period.
+///
+/// # Arguments
+///
+/// * `State(state)` - The shared state holding the named work schedules.
+/// * `Query(query)` - Query parameters containing start and end dates.
+/// * `Query(params)` - Query parameters for the calculation as for `get_workhours`.
+///
+/// # Returns
+///
+/// Returns a JSON response with the calculated work hours, or a problem+json error naming
+/// the invalid parameter.
+async fn get_workhours_v2(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<QueryParams>,
+    Query(params): Query<CalculationParams>,
+) -> impl IntoResponse {
+    let result = task::spawn_blocking(move || -> Result<_, ApiError> {
+        let options = state.calculation_options(&params)?;
+        let (start, end) = query.range(&options, state.clock.today())?;
+        Ok(calculate_report(start, end, &options)?)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(report)) => Json(report).into_response(),
+        Ok(Err(err)) => err.into_response(),
+        Err(_) => ApiError::Internal.into_response(),
+    }
+}
+
+/// Represents the query parameters for the work hours calculation.
+///
+/// The range is given either by `start` and `end` or by one of `month`, `year` or `period`.
+#[derive(Deserialize, Default)]
+struct QueryParams {
+    /// The start date for the work hours calculation (format: "DD-MM-YYYY", "YYYY-MM-DD",
+    /// "YYYYMMDD" or "YYYY-Www"), or a relative date such as "start-of-month" or "-2w".
+    start: Option<String>,
+    /// The end date for the work hours calculation, in the same formats as `start`.
+    end: Option<String>,
+    /// A whole month to calculate work hours for (format: "YYYY-MM").
+    month: Option<String>,
+    /// A whole year to calculate work hours for.
+    year: Option<i32>,
+    /// A whole reporting period to calculate work hours for by its id (format: "YYYY-MM-Www"),
+    /// e.g. "2024-03-W10".
+    period: Option<String>,
+    /// Whether to include the dates and working days of each period. Defaults to false.
+    #[serde(default)]
+    detail: bool,
+}
+
+impl QueryParams {
+    /// Calculates the work hours of a request.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameters controlling how the work hours are calculated.
+    /// * `state` - The shared state holding the named work schedules and the clock.
+    ///
+    /// # Returns
+    ///
+    /// Returns the work hours, with the details of each period if `detail` is set, or an
+    /// error naming the invalid parameter.
+    fn workhours(
+        &self,
+        params: &CalculationParams,
+        state: &AppState,
+    ) -> Result<WorkHours, ApiError> {
+        let options = state.calculation_options(params)?;
+        let (start, end) = self.range(&options, state.clock.today())?;
+        if self.detail {
+            Ok(calculate_workhours_detailed(start, end, &options)?)
+        } else {
+            Ok(calculate_workhours(start, end, &options)?)
+        }
+    }
+
+    /// Resolves the range of a request.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The calculation options, whose period scheme decides the dates of `period`
+    ///   and of relative dates such as "end-of-period".
+    /// * `today` - Today's date, which relative dates such as "today" or "+2w" are resolved from.
+    ///
+    /// # Returns
+    ///
+    /// Returns the start and end dates, or an error naming the parameter if the range is
+    /// missing, given in more than one way or invalid.
+    fn range(
+        &self,
+        options: &CalculationOptions,
+        today: NaiveDate,
+    ) -> Result<(String, String), ApiError> {
+        let iso = |(start, end): (NaiveDate, NaiveDate)| (start.to_string(), end.to_string());
+        let resolve = |date: &String, bound| -> Result<String, ApiError> {
+            let relative = resolve_relative_date(date, bound, today, &options.scheme)?;
+            Ok(relative.map_or_else(|| date.clone(), |date| date.to_string()))
+        };
+        match (&self.start, &self.end, &self.month, self.year, &self.period) {
+            (Some(start), Some(end), None, None, None) => Ok((
+                resolve(start, DateBound::Start)?,
+                resolve(end, DateBound::End)?,
+            )),
+            (None, None, Some(month), None, None) => Ok(iso(month_range(month)?)),
+            (None, None, None, Some(year), None) => Ok(iso(year_range(year)?)),
+            (None, None, None, None, Some(period)) => Ok(iso(period_range(period, options)?)),
+            (Some(_), None, None, None, None) => Err(ApiError::invalid("end", "Missing end date")),
+            (None, Some(_), None, None, None) => {
+                Err(ApiError::invalid("start", "Missing start date"))
+            }
+            (None, None, None, None, None) => Err(ApiError::invalid(
+                "start",
+                "Provide either start and end, month, year or period",
+            )),
+            (start, end, month, year, _) => {
+                //Name the first parameter that conflicts with an earlier one
+                let parameter = if month.is_some() && (start.is_some() || end.is_some()) {
+                    "month"
+                } else if year.is_some() && (start.is_some() || end.is_some() || month.is_some()) {
+                    "year"
+                } else {
+                    "period"
+                };
+                Err(ApiError::invalid(
+                    parameter,
+                    "Provide only one of start and end, month, year or period",
+                ))
+            }
+        }
+    }
+}
+
+/// Represents the query parameters controlling how work hours are calculated, shared by
+/// all endpoints calculating work hours.
+#[derive(Deserialize, Default)]
+struct CalculationParams {
+    /// The country whose holidays to exclude (SE, NO, DK or FI). Defaults to SE.
+    #[serde(default)]
+    country: Country,
+    /// Work hours on eves shortened by collective agreement, e.g. 4 (SE only).
+    eve_hours: Option<u32>,
+    /// A named work schedule or hours per weekday, e.g. "8,8,8,8,6,0,0".
+    schedule: Option<String>,
+    /// Date ranges with other schedules, e.g. "24-06-2024..15-08-2024:7,7,7,7,7,0,0".
+    overrides: Option<String>,
+    /// The employment rate in percent, e.g. 80. Defaults to 100.
+    rate: Option<f64>,
+    /// How the scaled hours of each day are rounded ("minute" or "quarter"). Defaults to "minute".
+    #[serde(default)]
+    rounding: Rounding,
+    /// How the range is split into periods (ubw, isoweek, month, biweekly or fiscal445).
+    /// Defaults to ubw.
+    scheme: Option<String>,
+    /// The first day of any bi-weekly period (format: "DD-MM-YYYY", "YYYY-MM-DD" or
+    /// "YYYYMMDD"), e.g. a sprint start. Required for the biweekly scheme.
+    anchor: Option<String>,
+    /// The ISO week the fiscal years of the fiscal445 scheme start in, e.g. 27. Defaults to 1.
+    fiscal_start_week: Option<u32>,
+    /// The date splitting the work hours into elapsed and remaining hours, in the same formats
+    /// as `start`, e.g. "today".
+    as_of: Option<String>,
+    /// Leave deducted from the work hours, e.g. "2024-07-08..2024-07-26:vacation;2024-06-10:vab:4".
+    absences: Option<String>,
+}
+
+/// Handles requests to look up the reporting period of a date.
+///
+/// # Arguments
+///
+/// * `State(state)` - The shared state holding the named work schedules.
+/// * `Query(query)` - Query parameters containing the date.
+/// * `Query(params)` - Query parameters for the calculation as for `get_workhours`.
+///
+/// # Returns
+///
+/// Returns a JSON response with the name, dates, month, year, working days and work hours
+/// of the period containing the date and of the periods before and after it, or a
+/// problem+json error.
+async fn get_period(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<PeriodQueryParams>,
+    Query(params): Query<CalculationParams>,
+) -> impl IntoResponse {
+    let result = task::spawn_blocking(move || -> Result<_, ApiError> {
+        let options = state.calculation_options(&params)?;
+        Ok(lookup_period(query.date, &options)?)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(lookup)) => Json(lookup).into_response(),
+        Ok(Err(err)) => err.into_response(),
+        Err(_) => ApiError::Internal.into_response(),
+    }
+}
+
+/// Handles requests to list all reporting periods of a year.
+///
+/// # Arguments
+///
+/// * `State(state)` - The shared state holding the named work schedules.
+/// * `Query(query)` - Query parameters containing the year and an optional output format.
+/// * `Query(params)` - Query parameters for the calculation as for `get_workhours`.
+///
+/// # Returns
+///
+/// Returns the numbered periods with their dates, working days and work hours as JSON,
+/// CSV or an iCalendar feed of period deadlines, or a problem+json error.
+async fn get_periods(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<PeriodsQueryParams>,
+    Query(params): Query<CalculationParams>,
+) -> impl IntoResponse {
+    let result = task::spawn_blocking(move || -> Result<_, ApiError> {
+        let options = state.calculation_options(&params)?;
+        Ok(period_calendar(query.year, &options)?)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(calendar)) => (
+            [(header::CONTENT_TYPE, query.format.content_type())],
+            calendar.render(query.format),
         )
             .into_response(),
-        Err(_) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({ "error": "Internal Server Error" })),
+        Ok(Err(err)) => err.into_response(),
+        Err(_) => ApiError::Internal.into_response(),
+    }
+}
+
+/// Represents the query parameters for listing the periods of a year.
+#[derive(Deserialize)]
+struct PeriodsQueryParams {
+    /// The year to list the periods of.
+    year: i32,
+    /// The output format (json, csv or ics). Defaults to json.
+    #[serde(default)]
+    format: PeriodFormat,
+}
+
+/// Represents the query parameters for the period lookup.
+#[derive(Deserialize)]
+struct PeriodQueryParams {
+    /// The date to look up the period of (format: "DD-MM-YYYY").
+    date: String,
+}
+
+/// Handles requests to calculate a flex-time balance.
+///
+/// Keeps a running balance of the hours reported each day minus the expected hours, by day
+/// and by period, capping the balance carried over at each month end.
+///
+/// # Arguments
+///
+/// * `State(state)` - The shared state holding the named work schedules.
+/// * `Query(query)` - Query parameters containing the range as for `get_workhours`.
+/// * `Query(flex)` - Query parameters containing the reported hours, the opening balance and
+///   the caps on carry-over.
+/// * `Query(params)` - Query parameters for the calculation as for `get_workhours`.
+///
+/// # Returns
+///
+/// Returns a JSON response with the flex balance, or a problem+json error naming the invalid
+/// parameter.
+async fn get_flex(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<QueryParams>,
+    Query(flex): Query<FlexQueryParams>,
+    Query(params): Query<CalculationParams>,
+) -> impl IntoResponse {
+    let result = task::spawn_blocking(move || -> Result<_, ApiError> {
+        let options = state.calculation_options(&params)?;
+        let (start, end) = query.range(&options, state.clock.today())?;
+        let reported = flex
+            .reported
+            .as_deref()
+            .unwrap_or_default()
+            .parse()
+            .map_err(|err| ApiError::invalid("reported", err))?;
+        let cap = |parameter: &'static str, cap: Option<Hours>| match cap {
+            Some(hours) if hours < Hours::ZERO => Err(ApiError::invalid(
+                parameter,
+                format!("Invalid {} {}: must not be negative", parameter, hours),
+            )),
+            cap => Ok(cap),
+        };
+        let rules = FlexRules {
+            opening_balance: flex.opening_balance.unwrap_or_default(),
+            max_carry_over: cap("max_carry_over", flex.max_carry_over)?,
+            max_deficit: cap("max_deficit", flex.max_deficit)?,
+        };
+        Ok(calculate_flex(start, end, &reported, &rules, &options)?)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(balance)) => Json(balance).into_response(),
+        Ok(Err(err)) => err.into_response(),
+        Err(_) => ApiError::Internal.into_response(),
+    }
+}
+
+/// Represents the query parameters for the flex-time balance.
+#[derive(Deserialize, Default)]
+struct FlexQueryParams {
+    /// The hours reported by date, e.g. "2024-06-03:8.5;2024-06-04:7:45".
+    reported: Option<String>,
+    /// The balance before the first day of the range in hours, e.g. -2.5. Defaults to 0.
+    opening_balance: Option<Hours>,
+    /// The largest surplus in hours carried over at the end of a month. Defaults to no cap.
+    max_carry_over: Option<Hours>,
+    /// The largest deficit in hours carried over at the end of a month. Defaults to no cap.
+    max_deficit: Option<Hours>,
+}
+
+/// Handles requests to classify overtime into simple and qualified overtime.
+///
+/// Time worked beyond the work hours of a day is simple overtime ("enkel övertid") on
+/// weekdays between 06:00 and 20:00, and qualified overtime ("kvalificerad övertid") in the
+/// evenings, at night, on weekends and on holidays, totalled by period.
+///
+/// # Arguments
+///
+/// * `State(state)` - The shared state holding the named work schedules.
+/// * `Query(query)` - Query parameters containing the range as for `get_workhours`.
+/// * `Query(overtime)` - Query parameters containing the time intervals worked.
+/// * `Query(params)` - Query parameters for the calculation as for `get_workhours`.
+///
+/// # Returns
+///
+/// Returns a JSON response with the overtime by period, or a problem+json error naming the
+/// invalid parameter.
+async fn get_overtime(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<QueryParams>,
+    Query(overtime): Query<OvertimeQueryParams>,
+    Query(params): Query<CalculationParams>,
+) -> impl IntoResponse {
+    let result = task::spawn_blocking(move || -> Result<_, ApiError> {
+        let options = state.calculation_options(&params)?;
+        let (start, end) = query.range(&options, state.clock.today())?;
+        let worked = overtime
+            .worked
+            .as_deref()
+            .unwrap_or_default()
+            .parse()
+            .map_err(|err| ApiError::invalid("worked", err))?;
+        Ok(calculate_overtime(start, end, &worked, &options)?)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(report)) => Json(report).into_response(),
+        Ok(Err(err)) => err.into_response(),
+        Err(_) => ApiError::Internal.into_response(),
+    }
+}
+
+/// Represents the query parameters for the overtime classification.
+#[derive(Deserialize, Default)]
+struct OvertimeQueryParams {
+    /// The time intervals worked by date, e.g. "2024-06-03:08:00-17:00;2024-06-03:19:00-22:30".
+    worked: Option<String>,
+}
+
+/// Handles requests to calculate the vacation days of a vacation year.
+///
+/// Counts the paid vacation days earned under the Swedish Annual Leave Act, the days used
+/// by whole-day vacation absences, the days left and how many of them may be saved, and
+/// optionally the vacation days a requested vacation between `start` and `end` consumes.
+///
+/// # Arguments
+///
+/// * `State(state)` - The shared state holding the named work schedules.
+/// * `Query(query)` - Query parameters containing the vacation year, an optional employment
+///   start, the days saved from earlier years, the vacation rules and an optional requested
+///   vacation.
+/// * `Query(params)` - Query parameters for the calculation as for `get_workhours`, whose
+///   absences reduce the days earned and use up days.
+///
+/// # Returns
+///
+/// Returns a JSON response with the vacation balance, or a problem+json error naming the
+/// invalid parameter.
+async fn get_vacation(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<VacationQueryParams>,
+    Query(params): Query<CalculationParams>,
+) -> impl IntoResponse {
+    let result = task::spawn_blocking(move || -> Result<_, ApiError> {
+        let options = state.calculation_options(&params)?;
+        let defaults = VacationRules::default();
+        let rules = VacationRules::new(
+            query.paid_days.unwrap_or(defaults.paid_days),
+            query.start_month.unwrap_or(defaults.start_month),
         )
-            .into_response(),
+        .map_err(|err| {
+            let parameter = if query
+                .start_month
+                .is_some_and(|month| !(1..=12).contains(&month))
+            {
+                "start_month"
+            } else {
+                "paid_days"
+            };
+            ApiError::invalid(parameter, err)
+        })?;
+        let employment_start = query
+            .employment_start
+            .as_deref()
+            .map(|date| {
+                workhours::parse_date(date, DateBound::Start)
+                    .map_err(|err| ApiError::invalid("employment_start", err.to_string()))
+            })
+            .transpose()?;
+        let requested = match (query.start, query.end) {
+            (Some(start), Some(end)) => Some((start, end)),
+            (Some(_), None) => return Err(ApiError::invalid("end", "Missing end date")),
+            (None, Some(_)) => return Err(ApiError::invalid("start", "Missing start date")),
+            (None, None) => None,
+        };
+        Ok(vacation_balance(
+            query.year,
+            employment_start,
+            query.saved_days.unwrap_or(0),
+            requested,
+            &rules,
+            &options,
+        )?)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(balance)) => Json(balance).into_response(),
+        Ok(Err(err)) => err.into_response(),
+        Err(_) => ApiError::Internal.into_response(),
     }
 }
 
-/// Represents the query parameters for the work hours calculation.
+/// Represents the query parameters for the vacation balance.
 #[derive(Deserialize)]
-struct QueryParams {
-    /// The start date for the work hours calculation (format: "DD-MM-YYYY").
-    start: String,
-    /// The end date for the work hours calculation (format: "DD-MM-YYYY").
-    end: String,
+struct VacationQueryParams {
+    /// The year the vacation year starts in.
+    year: i32,
+    /// The first day of employment, in the same formats as `start`.
+    employment_start: Option<String>,
+    /// The month the vacation year starts in. Defaults to 4 (April).
+    start_month: Option<u32>,
+    /// The paid vacation days of a full earning year. Defaults to 25.
+    paid_days: Option<u32>,
+    /// The paid vacation days saved from earlier years. Defaults to 0.
+    saved_days: Option<u32>,
+    /// The first day of a requested vacation, in any of the formats accepted by `get_workhours`.
+    start: Option<String>,
+    /// The last day of a requested vacation.
+    end: Option<String>,
+}
+
+/// Handles requests to list holidays.
+///
+/// Lists the holidays of the requested country (Sweden by default) either for a whole
+/// year or between a start and an end date.
+///
+/// # Arguments
+///
+/// * `Query(query)` - Query parameters containing a year or start and end dates, and an
+///   optional country and language.
+///
+/// # Returns
+///
+/// Returns a JSON array of holidays with their date, name and kind, or a problem+json error.
+async fn get_holidays(Query(query): Query<HolidayQueryParams>) -> impl IntoResponse {
+    let (start, end) = match (query.year, query.start, query.end) {
+        (Some(year), None, None) => match year_range(year) {
+            Ok((start, end)) => (start.to_string(), end.to_string()),
+            Err(err) => return ApiError::from(err).into_response(),
+        },
+        (None, Some(start), Some(end)) => (start, end),
+        _ => {
+            return ApiError::invalid("year", "Provide either year or both start and end")
+                .into_response()
+        }
+    };
+    if query.lang == Language::Sv && query.country != Country::Se {
+        return ApiError::invalid("lang", "lang=sv is only supported for SE, use lang=local")
+            .into_response();
+    }
+    let result = task::spawn_blocking(move || -> Result<_, ApiError> {
+        let calendar = holiday_calendar(query.country, query.eve_hours)?;
+        Ok(list_holidays(start, end, &*calendar)?)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(holidays)) => Json(
+            holidays
+                .iter()
+                .map(|holiday| HolidayResponse {
+                    date: holiday.date,
+                    name: holiday.localized_name(query.lang),
+                    kind: holiday.kind,
+                    hours: match holiday.kind {
+                        HolidayKind::ReducedHours(hours) => Some(hours),
+                        _ => None,
+                    },
+                })
+                .collect::<Vec<_>>(),
+        )
+        .into_response(),
+        Ok(Err(err)) => err.into_response(),
+        Err(_) => ApiError::Internal.into_response(),
+    }
+}
+
+/// Represents the query parameters for listing holidays.
+#[derive(Deserialize)]
+struct HolidayQueryParams {
+    /// The year to list holidays for. Cannot be combined with start and end.
+    year: Option<i32>,
+    /// The first date to list holidays from (format: "DD-MM-YYYY").
+    start: Option<String>,
+    /// The last date to list holidays to (format: "DD-MM-YYYY").
+    end: Option<String>,
+    /// The country whose holidays to list (SE, NO, DK or FI). Defaults to SE.
+    #[serde(default)]
+    country: Country,
+    /// The language of the holiday names ("local", "sv" for SE only, or "en"). Defaults to the
+    /// country's own language.
+    #[serde(default)]
+    lang: Language,
+    /// Work hours on eves shortened by collective agreement, e.g. 4 (SE only).
+    eve_hours: Option<u32>,
+}
+
+/// Represents a single holiday in the holidays response.
+#[derive(Serialize)]
+struct HolidayResponse<'a> {
+    /// The date of the holiday
+    date: NaiveDate,
+    /// The name of the holiday in the requested language
+    name: &'a str,
+    /// The kind of holiday
+    kind: HolidayKind,
+    /// The work hours of a reduced day
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hours: Option<u32>,
+}
+
+/// Builds the holiday calendar of a country.
+///
+/// # Arguments
+///
+/// * `country` - The country whose calendar to build.
+/// * `eve_hours` - Work hours on eves shortened by collective agreement. Only supported for Sweden.
+///
+/// # Returns
+///
+/// Returns the calendar, or an error naming the `eve_hours` parameter if reduced eves are
+/// requested for a country other than Sweden or the hours are out of range.
+fn holiday_calendar(
+    country: Country,
+    eve_hours: Option<u32>,
+) -> Result<Box<dyn HolidayCalendar + Send + Sync>, ApiError> {
+    match (country, eve_hours) {
+        (_, None) => Ok(country.calendar()),
+        (Country::Se, Some(hours)) if hours <= 24 => Ok(Box::new(
+            SwedishCalendar::default().with_reduced_eves(hours),
+        )),
+        (Country::Se, Some(_)) => Err(ApiError::invalid(
+            "eve_hours",
+            "eve_hours must be between 0 and 24",
+        )),
+        _ => Err(ApiError::invalid(
+            "eve_hours",
+            "eve_hours is only supported for SE",
+        )),
+    }
+}
+
+/// Builds the period scheme of a request.
+///
+/// # Arguments
+///
+/// * `scheme` - The name of the scheme. Defaults to the UBW scheme.
+/// * `anchor` - The first day of any bi-weekly period (format: "DD-MM-YYYY", "YYYY-MM-DD" or
+///   "YYYYMMDD"). Required for the bi-weekly scheme.
+/// * `fiscal_start_week` - The ISO week the fiscal years of the 4-4-5 scheme start in.
+///   Defaults to week 1.
+///
+/// # Returns
+///
+/// Returns the scheme, or an error naming the `scheme`, `anchor` or `fiscal_start_week`
+/// parameter if the name, anchor or week is invalid, the bi-weekly scheme has no anchor, or
+/// an anchor or week is given for a scheme that doesn't use it.
+fn period_scheme(
+    scheme: Option<&str>,
+    anchor: Option<&str>,
+    fiscal_start_week: Option<u32>,
+) -> Result<PeriodScheme, ApiError> {
+    let mut scheme: PeriodScheme = scheme
+        .map(str::parse)
+        .transpose()
+        .map_err(|err| ApiError::invalid("scheme", err))?
+        .unwrap_or_default();
+    match anchor {
+        Some(anchor) => {
+            scheme = workhours::parse_single_day(anchor)
+                .map_err(|err| match err {
+                    DateInputError::Invalid => format!("Invalid date '{}'", anchor),
+                    DateInputError::Ambiguous => format!("Ambiguous date '{}'", anchor),
+                })
+                .and_then(|anchor| scheme.with_anchor(anchor))
+                .map_err(|err| ApiError::invalid("anchor", err))?;
+        }
+        None if scheme == PeriodScheme::BiWeekly(None) => {
+            return Err(ApiError::invalid(
+                "anchor",
+                "The biweekly scheme requires an anchor, the first day of any of its periods",
+            ))
+        }
+        None => {}
+    }
+    match fiscal_start_week {
+        Some(week) => scheme
+            .with_fiscal_start_week(week)
+            .map_err(|err| ApiError::invalid("fiscal_start_week", err)),
+        None => Ok(scheme),
+    }
 }
 
 #[cfg(test)]
@@ -264,19 +1282,863 @@ mod tests {
     use super::*;
     use axum::http::StatusCode;
     use axum::response::Response;
+    use workhours::Hours;
+
+    /// Reads the JSON body of a response
+    async fn json_body(response: Response) -> serde_json::Value {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    /// Reads query parameters of any shape from a URI
+    fn query<T: serde::de::DeserializeOwned>(uri: &http::Uri) -> Query<T> {
+        Query::try_from_uri(uri).unwrap()
+    }
+
+    /// Calls the GET handler of the path of a URI with the query parameters of the URI
+    async fn get(uri: &str) -> Response {
+        let uri: http::Uri = uri.parse().unwrap();
+        let state = State(Arc::default());
+        match uri.path() {
+            "/api/v1/workhours" => get_workhours(state, query(&uri), query(&uri))
+                .await
+                .into_response(),
+            "/api/v1/vacation" => get_vacation(state, query(&uri), query(&uri))
+                .await
+                .into_response(),
+            "/api/v1/flex" => get_flex(state, query(&uri), query(&uri), query(&uri))
+                .await
+                .into_response(),
+            "/api/v1/overtime" => get_overtime(state, query(&uri), query(&uri), query(&uri))
+                .await
+                .into_response(),
+            path => panic!("no GET handler for {}", path),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invalid_parameters() {
+        let cases = [
+            (
+                "/api/v1/workhours?start=01-01-2024&end=31-01-2024&rate=120&rounding=quarter",
+                "invalid_parameter",
+                "rate",
+            ),
+            (
+                "/api/v1/workhours?start=31-01-2024&end=01-01-2024",
+                "reversed_range",
+                "end",
+            ),
+            (
+                "/api/v1/workhours?start=01-01-1500&end=02-01-1500",
+                "invalid_start",
+                "start",
+            ),
+            ("/api/v1/workhours?year=1500", "invalid_year", "year"),
+            (
+                "/api/v1/workhours?month=2024-06&absences=2024-06-10:holiday",
+                "invalid_parameter",
+                "absences",
+            ),
+            (
+                "/api/v1/vacation?year=2024&start_month=13",
+                "invalid_parameter",
+                "start_month",
+            ),
+            (
+                "/api/v1/flex?month=2024-06&reported=2024-06-03",
+                "invalid_parameter",
+                "reported",
+            ),
+            (
+                "/api/v1/overtime?month=2024-06&worked=2024-06-03:08:00",
+                "invalid_parameter",
+                "worked",
+            ),
+            (
+                "/api/v1/overtime?month=2024-06&worked=2024-07-01:08:00-17:00",
+                "worked_outside_range",
+                "worked",
+            ),
+        ];
+        for (uri, code, parameter) in cases {
+            let response = get(uri).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", uri);
+            let body = json_body(response).await;
+            assert_eq!(body["code"], code, "{}", uri);
+            assert_eq!(body["parameter"], parameter, "{}", uri);
+        }
+    }
 
     #[tokio::test]
     async fn test_get_workhours() {
         let query = Query(QueryParams {
-            start: "01-01-2023".to_string(),
-            end: "31-12-2023".to_string(),
+            start: Some("01-01-2023".to_string()),
+            end: Some("31-12-2023".to_string()),
+            detail: false,
+            ..Default::default()
         });
+        let params = Query(CalculationParams::default());
 
-        let response: Response = get_workhours(query).await.into_response();
+        let response: Response = get_workhours(State(Arc::default()), query, params)
+            .await
+            .into_response();
         assert_eq!(response.status(), StatusCode::OK);
 
         // You might want to add more assertions here to check the response body
     }
 
+    #[tokio::test]
+    async fn test_get_workhours_detail() {
+        let query = Query(QueryParams {
+            start: Some("05-06-2024".to_string()),
+            end: Some("13-06-2024".to_string()),
+            detail: true,
+            ..Default::default()
+        });
+        let params = Query(CalculationParams::default());
+
+        let response: Response = get_workhours(State(Arc::default()), query, params)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = json_body(response).await;
+        assert_eq!(
+            body["2024"]["06-June"]["2024-06-W23"],
+            serde_json::json!({
+                "id": "2024-06-W23",
+                "start": "2024-06-05",
+                "end": "2024-06-09",
+                "period_start": "2024-06-01",
+                "period_end": "2024-06-09",
+                "iso_week": 23,
+                "working_days": 2,
+                "hours": 16
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_workhours_v2() {
+        let query = Query(QueryParams {
+            start: Some("28-12-2023".to_string()),
+            end: Some("10-01-2024".to_string()),
+            detail: false,
+            ..Default::default()
+        });
+        let params = Query(CalculationParams::default());
+
+        let response: Response = get_workhours_v2(State(Arc::default()), query, params)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = json_body(response).await;
+        assert_eq!(body["years"][0]["year"], 2023);
+        assert_eq!(body["years"][1]["months"][0]["month"], 1);
+        assert_eq!(body["years"][1]["months"][0]["name"], "January");
+        assert_eq!(body["years"][1]["months"][0]["periods"][1]["index"], 2);
+        assert_eq!(
+            body["years"][1]["months"][0]["periods"][1]["name"],
+            "week: 2"
+        );
+        assert_eq!(
+            body["years"][1]["months"][0]["periods"][1]["id"],
+            "2024-01-W02"
+        );
+        assert_eq!(body["total"], 8 * 9);
+    }
+
+    #[tokio::test]
+    async fn test_get_period() {
+        let query = Query(PeriodQueryParams {
+            date: "05-06-2024".to_string(),
+        });
+        let params = Query(CalculationParams {
+            rate: Some(50.0),
+            ..Default::default()
+        });
+
+        let response: Response = get_period(State(Arc::default()), query, params)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = json_body(response).await;
+        assert_eq!(body["id"], "2024-06-W23");
+        assert_eq!(body["name"], "week: 23");
+        assert_eq!(body["year"], 2024);
+        assert_eq!(body["month"], 6);
+        assert_eq!(body["period_start"], "2024-06-01");
+        assert_eq!(body["hours"], 4 * 4);
+        assert_eq!(body["previous"]["period_end"], "2024-05-31");
+        assert_eq!(body["next"]["name"], "week: 24");
+    }
+
+    #[tokio::test]
+    async fn test_get_periods_csv() {
+        let query = Query(PeriodsQueryParams {
+            year: 2024,
+            format: PeriodFormat::Csv,
+        });
+
+        let response: Response = get_periods(
+            State(Arc::default()),
+            query,
+            Query(CalculationParams::default()),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/csv; charset=utf-8"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_workhours_norway() {
+        let query = Query(QueryParams {
+            start: Some("01-05-2024".to_string()),
+            end: Some("31-05-2024".to_string()),
+            detail: false,
+            ..Default::default()
+        });
+        let params = Query(CalculationParams {
+            country: Country::No,
+            ..Default::default()
+        });
+
+        let response: Response = get_workhours(State(Arc::default()), query, params)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_get_holidays_for_year() {
+        let query = Query(HolidayQueryParams {
+            year: Some(2024),
+            start: None,
+            end: None,
+            country: Country::Se,
+            lang: Language::En,
+            eve_hours: Some(4),
+        });
+
+        let response: Response = get_holidays(query).await.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_get_holidays_requires_a_range() {
+        let query = Query(HolidayQueryParams {
+            year: Some(2024),
+            start: Some("01-01-2024".to_string()),
+            end: None,
+            country: Country::Se,
+            lang: Language::Local,
+            eve_hours: None,
+        });
+
+        let response: Response = get_holidays(query).await.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_get_holidays_reports_an_invalid_year() {
+        let query = Query(HolidayQueryParams {
+            year: Some(99999),
+            start: None,
+            end: None,
+            country: Country::Se,
+            lang: Language::Local,
+            eve_hours: None,
+        });
+
+        let response: Response = get_holidays(query).await.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = json_body(response).await;
+        assert_eq!(body["code"], "invalid_year");
+        assert_eq!(body["parameter"], "year");
+    }
+
+    #[tokio::test]
+    async fn test_get_holidays_swedish_only_for_sweden() {
+        let query = Query(HolidayQueryParams {
+            year: Some(2024),
+            start: None,
+            end: None,
+            country: Country::No,
+            lang: Language::Sv,
+            eve_hours: None,
+        });
+
+        let response: Response = get_holidays(query).await.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_eve_hours_only_for_sweden() {
+        let query = Query(QueryParams {
+            start: Some("01-01-2024".to_string()),
+            end: Some("31-01-2024".to_string()),
+            detail: false,
+            ..Default::default()
+        });
+        let params = Query(CalculationParams {
+            country: Country::Fi,
+            eve_hours: Some(4),
+            ..Default::default()
+        });
+
+        let response: Response = get_workhours(State(Arc::default()), query, params)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_get_workhours_named_schedule() {
+        let state = AppState {
+            schedules: HashMap::from([(
+                "fourdays".to_string(),
+                WorkSchedule::from_hours([8, 8, 8, 8, 0, 0, 0]),
+            )]),
+            ..Default::default()
+        };
+        let query = Query(QueryParams {
+            start: Some("01-01-2024".to_string()),
+            end: Some("31-01-2024".to_string()),
+            detail: false,
+            ..Default::default()
+        });
+        let params = Query(CalculationParams {
+            schedule: Some("fourdays".to_string()),
+            ..Default::default()
+        });
+
+        let response: Response = get_workhours(State(Arc::new(state)), query, params)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_get_workhours_invalid_end() {
+        let query = Query(QueryParams {
+            start: Some("01-01-2024".to_string()),
+            end: Some("31-02-2024".to_string()),
+            detail: false,
+            ..Default::default()
+        });
+
+        let response: Response = get_workhours(
+            State(Arc::default()),
+            query,
+            Query(CalculationParams::default()),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/problem+json"
+        );
+
+        let body = json_body(response).await;
+        assert_eq!(body["type"], "urn:time-tally:problem:invalid_end");
+        assert_eq!(body["status"], 400);
+        assert_eq!(body["code"], "invalid_end");
+        assert_eq!(body["parameter"], "end");
+    }
+
+    #[tokio::test]
+    async fn test_get_workhours_month_shorthand() {
+        let query = Query(QueryParams {
+            month: Some("2024-02".to_string()),
+            ..Default::default()
+        });
+        let params = Query(CalculationParams::default());
+
+        let response: Response = get_workhours_v2(State(Arc::default()), query, params)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = json_body(response).await;
+        assert_eq!(body["start"], "2024-02-01");
+        assert_eq!(body["end"], "2024-02-29");
+        assert_eq!(body["total"], 8 * 21);
+    }
+
+    #[tokio::test]
+    async fn test_get_workhours_relative_dates() {
+        let state = AppState {
+            clock: Clock::Fixed(NaiveDate::from_ymd_opt(2024, 6, 5).unwrap()),
+            ..Default::default()
+        };
+        let query = Query(QueryParams {
+            start: Some("today".to_string()),
+            end: Some("end-of-month".to_string()),
+            ..Default::default()
+        });
+        let params = Query(CalculationParams::default());
+
+        let response: Response = get_workhours_v2(State(Arc::new(state)), query, params)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = json_body(response).await;
+        assert_eq!(body["start"], "2024-06-05");
+        assert_eq!(body["end"], "2024-06-30");
+        //nationaldagen on 6 june and midsommarafton on 21 june are off
+        assert_eq!(body["total"], 8 * 16);
+    }
+
+    #[tokio::test]
+    async fn test_get_workhours_as_of() {
+        let state = AppState {
+            clock: Clock::Fixed(NaiveDate::from_ymd_opt(2024, 6, 12).unwrap()),
+            ..Default::default()
+        };
+        let query = Query(QueryParams {
+            month: Some("2024-06".to_string()),
+            ..Default::default()
+        });
+        let params = Query(CalculationParams {
+            as_of: Some("today".to_string()),
+            ..Default::default()
+        });
+
+        let response: Response = get_workhours_v2(State(Arc::new(state)), query, params)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = json_body(response).await;
+        assert_eq!(body["elapsed"], 8 * 6);
+        assert_eq!(body["remaining"], 8 * 12);
+        let month = &body["years"][0]["months"][0];
+        assert_eq!(month["elapsed"], 8 * 6);
+        assert_eq!(month["periods"][1]["elapsed"], 8 * 2);
+        assert_eq!(month["periods"][1]["remaining"], 8 * 3);
+    }
+
+    #[tokio::test]
+    async fn test_post_workhours_batch() {
+        let items: Vec<BatchItem> = serde_json::from_value(serde_json::json!([
+            { "id": "june", "month": "2024-06" },
+            { "id": 2, "start": "01-07-2024", "end": "31-13-2024" },
+            { "start": "2024-07-01", "end": "2024-07-05", "rate": 50 }
+        ]))
+        .unwrap();
+
+        let response: Response = post_workhours_batch(State(Arc::default()), Ok(Json(items)))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = json_body(response).await;
+        assert_eq!(body[0]["id"], "june");
+        assert_eq!(body[0]["result"]["total"], 8 * 18);
+        assert_eq!(body[1]["id"], 2);
+        assert_eq!(body[1]["error"]["code"], "invalid_end");
+        assert_eq!(body[1]["error"]["parameter"], "end");
+        assert!(body[2].get("id").is_none());
+        assert_eq!(body[2]["result"]["total"], 4 * 5);
+    }
+
+    #[tokio::test]
+    async fn test_post_workhours_batch_too_large() {
+        let state = AppState {
+            max_batch_size: 1,
+            ..Default::default()
+        };
+        let items: Vec<BatchItem> = serde_json::from_value(serde_json::json!([
+            { "month": "2024-06" },
+            { "month": "2024-07" }
+        ]))
+        .unwrap();
+
+        let response: Response = post_workhours_batch(State(Arc::new(state)), Ok(Json(items)))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        let body = json_body(response).await;
+        assert_eq!(body["code"], "batch_too_large");
+    }
+
+    #[tokio::test]
+    async fn test_post_reconcile() {
+        let request: ReconcileRequest = serde_json::from_value(serde_json::json!({
+            "month": "2024-06",
+            "reported": {
+                "2024-06-W23": 32,
+                "2024-06-10": 7.5,
+                "2024-06-11": 8,
+                "2024-06-W25": 40
+            }
+        }))
+        .unwrap();
+
+        let response: Response = post_reconcile(State(Arc::default()), Ok(Json(request)))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = json_body(response).await;
+        let periods = &body["periods"];
+        assert_eq!(periods[0]["id"], "2024-06-W23");
+        assert_eq!(periods[0]["status"], "balanced");
+        assert_eq!(periods[1]["reported"], 15.5);
+        assert_eq!(periods[1]["difference"], -24.5);
+        assert_eq!(periods[1]["status"], "under");
+        //midsommarafton on 21 june is off
+        assert_eq!(periods[2]["expected"], 32);
+        assert_eq!(periods[2]["status"], "over");
+        assert_eq!(body["expected"], 8 * 18);
+    }
+
+    #[tokio::test]
+    async fn test_post_reconcile_invalid_reported() {
+        let cases = [
+            (
+                serde_json::json!({ "start": "2024-06-03", "end": "2024-06-09", "reported": { "2024-06-10": 8 } }),
+                "reported_outside_range",
+            ),
+            //the period of week 23 starts on 1 june, before the range
+            (
+                serde_json::json!({ "start": "2024-06-05", "end": "2024-06-09", "reported": { "2024-06-W23": 40 } }),
+                "reported_outside_range",
+            ),
+            (
+                serde_json::json!({ "month": "2024-06", "reported": { "2024-06-03": -40 } }),
+                "invalid_reported_hours",
+            ),
+            (
+                serde_json::json!({ "month": "2024-06", "reported": { "2024-06-03": 25 } }),
+                "invalid_reported_hours",
+            ),
+        ];
+        for (request, code) in cases {
+            let request: ReconcileRequest = serde_json::from_value(request).unwrap();
+            let response: Response = post_reconcile(State(Arc::default()), Ok(Json(request)))
+                .await
+                .into_response();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", code);
+
+            let body = json_body(response).await;
+            assert_eq!(body["code"], code);
+            assert_eq!(body["parameter"], "reported");
+        }
+
+        //hours that would overflow are rejected with the body
+        assert!(
+            serde_json::from_value::<ReconcileRequest>(serde_json::json!({
+                "month": "2024-06",
+                "reported": { "2024-06-03": 1e17, "2024-06-04": 1e17 }
+            }))
+            .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_workhours_absences() {
+        let query = Query(QueryParams {
+            start: Some("2024-06-10".to_string()),
+            end: Some("2024-06-16".to_string()),
+            detail: true,
+            ..Default::default()
+        });
+        let params = Query(CalculationParams {
+            absences: Some("2024-06-10..2024-06-11:vacation;2024-06-13:vab:4".to_string()),
+            ..Default::default()
+        });
+
+        let response: Response = get_workhours(State(Arc::default()), query, params)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = json_body(response).await;
+        let period = &body["2024"]["06-June"]["2024-06-W24"];
+        assert_eq!(period["hours"], 8 * 5 - 8 * 2 - 4);
+        assert_eq!(period["working_days"], 3);
+        assert_eq!(
+            period["absence"],
+            serde_json::json!({ "vacation": 16, "vab": 4 })
+        );
+        assert_eq!(body["absence"]["vacation"], 16);
+    }
+
+    #[tokio::test]
+    async fn test_get_vacation() {
+        let query = Query(VacationQueryParams {
+            year: 2024,
+            employment_start: Some("2023-10-01".to_string()),
+            start_month: None,
+            paid_days: None,
+            saved_days: Some(2),
+            start: Some("2024-07-01".to_string()),
+            end: Some("2024-07-12".to_string()),
+        });
+        let params = Query(CalculationParams {
+            absences: Some("2024-06-17..2024-06-20:vacation".to_string()),
+            ..Default::default()
+        });
+
+        let response: Response = get_vacation(State(Arc::default()), query, params)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = json_body(response).await;
+        assert_eq!(body["paid_days"], 13);
+        assert_eq!(body["used_days"], 4);
+        assert_eq!(body["saved_days"], 2);
+        assert_eq!(body["remaining_days"], 9 + 2);
+        assert_eq!(body["requested"]["days"], 10);
+        assert_eq!(body["requested"]["covered"], true);
+    }
+
+    #[tokio::test]
+    async fn test_get_flex() {
+        let response = get("/api/v1/flex?start=2024-05-30&end=2024-06-04\
+            &reported=2024-05-30:12;2024-05-31:9;2024-06-03:7:30\
+            &opening_balance=-0.5&max_carry_over=3")
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = json_body(response).await;
+        assert_eq!(body["month_ends"][0]["balance"], 4.5);
+        assert_eq!(body["month_ends"][0]["carried_over"], 3);
+        assert_eq!(body["periods"][0]["balance"], 3);
+        assert_eq!(body["days"][4]["balance"], 2.5);
+        assert_eq!(body["closing_balance"], -5.5);
+    }
+
+    #[test]
+    fn test_flex_hours_are_bounded() {
+        for parameter in ["opening_balance", "max_carry_over", "max_deficit"] {
+            let uri: http::Uri = format!("/api/v1/flex?{}=1e17", parameter).parse().unwrap();
+            assert!(
+                Query::<FlexQueryParams>::try_from_uri(&uri).is_err(),
+                "{}",
+                parameter
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_overtime() {
+        let response = get("/api/v1/overtime?start=2024-06-03&end=2024-06-09\
+            &worked=2024-06-03:07:00-21:30;2024-06-06:10:00-12:00;2024-06-08:22:00-01:00")
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = json_body(response).await;
+        assert_eq!(body["periods"][0]["id"], "2024-06-W23");
+        assert_eq!(body["simple"], 5);
+        assert_eq!(body["qualified"], 6.5);
+    }
+
+    #[test]
+    fn test_range_resolution() {
+        let options = CalculationOptions::default();
+        let range = |query: QueryParams| {
+            query.range(&options, NaiveDate::from_ymd_opt(2024, 6, 5).unwrap())
+        };
+        let text = |value: &str| Some(value.to_string());
+
+        assert_eq!(
+            range(QueryParams {
+                start: text("2024-W10"),
+                end: text("20240310"),
+                ..Default::default()
+            }),
+            Ok(("2024-W10".to_string(), "20240310".to_string()))
+        );
+        assert_eq!(
+            range(QueryParams {
+                year: Some(2024),
+                ..Default::default()
+            }),
+            Ok(("2024-01-01".to_string(), "2024-12-31".to_string()))
+        );
+        assert_eq!(
+            range(QueryParams {
+                period: text("2024-03-W11"),
+                ..Default::default()
+            }),
+            Ok(("2024-03-11".to_string(), "2024-03-17".to_string()))
+        );
+        assert_eq!(
+            range(QueryParams {
+                start: text("01-03-2024"),
+                month: text("2024-03"),
+                ..Default::default()
+            })
+            .map_err(|err| err.parameter()),
+            Err(Some("month"))
+        );
+        assert_eq!(
+            range(QueryParams {
+                month: text("2024-13"),
+                ..Default::default()
+            })
+            .map_err(|err| err.code()),
+            Err("invalid_month")
+        );
+        assert!(range(QueryParams::default()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_workhours_month_scheme() {
+        let query = Query(QueryParams {
+            start: Some("01-01-2024".to_string()),
+            end: Some("31-03-2024".to_string()),
+            detail: false,
+            ..Default::default()
+        });
+        let params = Query(CalculationParams {
+            scheme: Some("month".to_string()),
+            ..Default::default()
+        });
+
+        let response: Response = get_workhours(State(Arc::default()), query, params)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = json_body(response).await;
+        assert_eq!(body["2024"]["02-February"]["2024-02-W09"], 8 * 21);
+    }
+
+    #[tokio::test]
+    async fn test_get_period_with_ubw_rules() {
+        let state = AppState {
+            ubw_rules: UbwRules {
+                trailing_merge_days: 0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let query = Query(PeriodQueryParams {
+            date: "30-04-2024".to_string(),
+        });
+
+        let response: Response = get_period(
+            State(Arc::new(state)),
+            query,
+            Query(CalculationParams::default()),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = json_body(response).await;
+        assert_eq!(body["period_start"], "2024-04-29");
+        assert_eq!(body["previous"]["period_end"], "2024-04-28");
+    }
+
+    #[test]
+    fn test_period_scheme_resolution() {
+        assert_eq!(period_scheme(None, None, None), Ok(PeriodScheme::default()));
+        assert_eq!(
+            period_scheme(Some("biweekly"), Some("08-01-2024"), None),
+            Ok(PeriodScheme::BiWeekly(NaiveDate::from_ymd_opt(2024, 1, 8)))
+        );
+        assert_eq!(
+            period_scheme(Some("fiscal445"), None, Some(27)),
+            Ok(PeriodScheme::Fiscal445(27))
+        );
+        assert!(period_scheme(Some("month"), Some("08-01-2024"), None).is_err());
+        assert_eq!(
+            period_scheme(Some("biweekly"), Some("2024-01-08"), None),
+            Ok(PeriodScheme::BiWeekly(NaiveDate::from_ymd_opt(2024, 1, 8)))
+        );
+        assert!(period_scheme(Some("biweekly"), Some("2024-W02"), None).is_err());
+        assert!(period_scheme(Some("biweekly"), Some("08/01/2024"), None).is_err());
+        assert!(period_scheme(Some("quarter"), None, None).is_err());
+        assert!(period_scheme(Some("ubw"), None, Some(27)).is_err());
+        assert!(period_scheme(Some("fiscal445"), None, Some(53)).is_err());
+        assert_eq!(
+            period_scheme(Some("biweekly"), None, None),
+            Err(ApiError::invalid(
+                "anchor",
+                "The biweekly scheme requires an anchor, the first day of any of its periods"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_work_schedule_resolution() {
+        let state = AppState {
+            schedules: HashMap::from([(
+                "fourdays".to_string(),
+                WorkSchedule::from_hours([8, 8, 8, 8, 0, 0, 0]),
+            )]),
+            ..Default::default()
+        };
+        assert_eq!(state.work_schedule(None), Ok(WorkSchedule::default()));
+        assert_eq!(
+            state.work_schedule(Some("fourdays")),
+            Ok(WorkSchedule::from_hours([8, 8, 8, 8, 0, 0, 0]))
+        );
+        assert_eq!(
+            state.work_schedule(Some("8,8,8,8,6,0,0")),
+            Ok(WorkSchedule::from_hours([8, 8, 8, 8, 6, 0, 0]))
+        );
+        assert!(state.work_schedule(Some("parttime")).is_err());
+    }
+
+    #[test]
+    fn test_schedule_plan_resolution() {
+        let state = AppState {
+            schedules: HashMap::from([(
+                "summer".to_string(),
+                WorkSchedule::from_hours([7, 7, 7, 7, 7, 0, 0]),
+            )]),
+            ..Default::default()
+        };
+        let date = |day, month| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+
+        let plan = state
+            .schedule_plan(
+                None,
+                Some("24-06-2024..15-08-2024:summer;05-08-2024..09-08-2024:0,0,0,0,0,0,0"),
+            )
+            .unwrap();
+        assert_eq!(plan.segments().len(), 2);
+        assert_eq!(plan.schedule_on(date(1, 7)), &state.schedules["summer"]);
+        assert_eq!(plan.hours_on(date(6, 8)), Hours::ZERO);
+        assert_eq!(plan.hours_on(date(16, 8)), Hours::from_hours(8));
+
+        let plan = state
+            .schedule_plan(None, Some("2024-06-24..20240815:summer"))
+            .unwrap();
+        assert_eq!(plan.schedule_on(date(1, 7)), &state.schedules["summer"]);
+
+        for invalid in [
+            "24-06-2024:summer",
+            "24-06-2024..15-08-2024",
+            "15-08-2024..24-06-2024:summer",
+            "24-06-2024..15-08-2024:winter",
+            "2024-W26..2024-W33:summer",
+        ] {
+            assert!(
+                state.schedule_plan(None, Some(invalid)).is_err(),
+                "{}",
+                invalid
+            );
+        }
+    }
+
     // TODO: Add more tests as needed
 }
@@ -1,5 +1,8 @@
 use time_tally::args::parse_args;
-use time_tally::{run_api_server, run_metrics_server, setup_tracing_subscriber};
+use time_tally::workhours::Clock;
+use time_tally::{
+    run_api_server, run_command, run_metrics_server, setup_tracing_subscriber, AppState,
+};
 use tokio::signal;
 
 #[tokio::main]
@@ -12,9 +15,32 @@ async fn main() {
         }
     };
 
+    let state = AppState {
+        schedules: args.schedules,
+        ubw_rules: args.ubw,
+        clock: Clock::System,
+        max_batch_size: args.max_batch_size,
+    };
+
+    if let Some(command) = args.command {
+        match run_command(command, &state) {
+            Ok(output) => print!("{}", output),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
     setup_tracing_subscriber(args.subscriber, args.verbose);
 
-    run_api_server(args.api_network.to_string(), args.api_port.to_string()).await;
+    run_api_server(
+        args.api_network.to_string(),
+        args.api_port.to_string(),
+        state,
+    )
+    .await;
 
     if args.metrics {
         run_metrics_server(
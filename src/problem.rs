@@ -0,0 +1,159 @@
+//! Error responses of the API.
+//!
+//! Errors are returned as RFC 7807 problem details (`application/problem+json`) with a
+//! stable, machine-readable `code` and, where known, the query `parameter` that caused them:
+//!
+//! ```json
+//! {
+//!   "type": "urn:time-tally:problem:invalid_end",
+//!   "title": "Bad Request",
+//!   "status": 400,
+//!   "detail": "Invalid end date '31-02-2024': expected DD-MM-YYYY, YYYY-MM-DD, YYYYMMDD or YYYY-Www from 1583 to 9999",
+//!   "code": "invalid_end",
+//!   "parameter": "end"
+//! }
+//! ```
+
+use crate::workhours::WorkhoursError;
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use std::fmt;
+
+/// An error response of the API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiError {
+    /// A calculation failed.
+    Workhours(WorkhoursError),
+    /// A query parameter is missing, malformed or not allowed in combination with others.
+    InvalidParameter {
+        /// The name of the query parameter.
+        parameter: &'static str,
+        /// A description of what is wrong with it.
+        message: String,
+    },
+    /// The request body is not valid JSON of the expected shape.
+    InvalidBody(String),
+    /// A batch request has more items than the server allows.
+    BatchTooLarge {
+        /// The number of items in the request
+        size: usize,
+        /// The largest number of items allowed
+        max: usize,
+    },
+    /// The request could not be completed, e.g. because a task panicked.
+    Internal,
+}
+
+impl ApiError {
+    /// Creates an error for an invalid query parameter.
+    pub fn invalid(parameter: &'static str, message: impl Into<String>) -> Self {
+        ApiError::InvalidParameter {
+            parameter,
+            message: message.into(),
+        }
+    }
+
+    /// Returns the HTTP status of the error.
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::Workhours(WorkhoursError::Calendar(_)) | ApiError::Internal => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+            ApiError::BatchTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            ApiError::Workhours(_)
+            | ApiError::InvalidParameter { .. }
+            | ApiError::InvalidBody(_) => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    /// Returns a stable, machine-readable code for the error, e.g. "invalid_start".
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::Workhours(err) => err.code(),
+            ApiError::InvalidParameter { .. } => "invalid_parameter",
+            ApiError::InvalidBody(_) => "invalid_body",
+            ApiError::BatchTooLarge { .. } => "batch_too_large",
+            ApiError::Internal => "internal_error",
+        }
+    }
+
+    /// Returns the name of the query parameter that caused the error, if any.
+    pub fn parameter(&self) -> Option<&'static str> {
+        match self {
+            ApiError::Workhours(err) => err.parameter(),
+            ApiError::InvalidParameter { parameter, .. } => Some(parameter),
+            ApiError::InvalidBody(_) | ApiError::BatchTooLarge { .. } | ApiError::Internal => None,
+        }
+    }
+
+    /// Returns the problem details of the error, e.g. to report the errors of batch items.
+    pub(crate) fn problem(&self) -> Problem {
+        let status = self.status();
+        Problem {
+            kind: format!("urn:time-tally:problem:{}", self.code()),
+            title: status.canonical_reason().unwrap_or("Error"),
+            status: status.as_u16(),
+            detail: self.to_string(),
+            code: self.code(),
+            parameter: self.parameter(),
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::Workhours(err) => err.fmt(f),
+            ApiError::InvalidParameter { message, .. } => f.write_str(message),
+            ApiError::InvalidBody(message) => write!(f, "Invalid request body: {}", message),
+            ApiError::BatchTooLarge { size, max } => write!(
+                f,
+                "The batch of {} items is larger than the maximum of {} items",
+                size, max
+            ),
+            ApiError::Internal => f.write_str("Internal Server Error"),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl From<WorkhoursError> for ApiError {
+    fn from(err: WorkhoursError) -> Self {
+        ApiError::Workhours(err)
+    }
+}
+
+/// The body of a problem details response.
+#[derive(Serialize, Debug)]
+pub(crate) struct Problem {
+    /// A URI identifying the kind of problem.
+    #[serde(rename = "type")]
+    kind: String,
+    /// The reason phrase of the status.
+    title: &'static str,
+    /// The HTTP status.
+    status: u16,
+    /// A description of this occurrence of the problem.
+    detail: String,
+    /// A stable, machine-readable code for the problem.
+    code: &'static str,
+    /// The query parameter that caused the problem.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parameter: Option<&'static str>,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (
+            self.status(),
+            [(header::CONTENT_TYPE, "application/problem+json")],
+            Json(self.problem()),
+        )
+            .into_response()
+    }
+}
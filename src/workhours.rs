@@ -1,17 +1,72 @@
 //! # Work Hours Calculator
 //!
 //! This module provides functionality for calculating work hours between two dates,
-//! taking into account weekends and Swedish holidays.
+//! taking into account a weekly `WorkSchedule` (8 hours Monday to Friday by default)
+//! and the holidays of a `HolidayCalendar` (Swedish by default).
 //!
 //! ## Key Concepts
 //!
 //! - **Reporting Period**: A span of time, typically a week, for which work hours are calculated.
-//! - **Work Hours**: The number of working hours in a period, excluding weekends and holidays.
+//! - **Period Scheme**: How a range is split into reporting periods, by default UBW weeks that
+//!   never cross a month break.
+//! - **Work Schedule**: The normal work time on each day of the week, optionally changing over
+//!   date ranges such as summer hours.
+//! - **Employment Rate**: The share of full time worked, scaling the work time of each day.
+//! - **Absence**: Leave such as vacation or sick leave, deducted from the work hours of the
+//!   days it covers.
+//! - **Flex Time**: The running balance of reported hours minus work hours.
+//! - **Work Hours**: The number of working hours in a period according to the schedule, excluding
+//!   holidays, and with the shortened hours of reduced days such as eves ("halvdagar").
+
+mod absence;
+mod clock;
+mod date_input;
+mod error;
+mod flex;
+pub mod holidays;
+mod hours;
+mod overtime;
+mod period_calendar;
+mod period_id;
+mod progress;
+mod reconcile;
+mod relative;
+mod report;
+mod schedule;
+mod scheme;
+mod vacation;
 
 use chrono::{Datelike, Duration, NaiveDate};
+use date_input::{parse_date_input, parse_month, year_bounds};
+use holidays::{Holiday, HolidayCalendar, SwedishCalendar};
 use serde::Serialize;
 use std::{cmp, collections::BTreeMap};
 
+pub use absence::{Absence, AbsenceHours, AbsenceKind};
+pub use clock::Clock;
+pub use date_input::DateBound;
+pub(crate) use date_input::{parse_single_day, DateInputError};
+pub use error::{WorkhoursError, MAX_RANGE_DAYS, SUPPORTED_YEARS};
+pub use flex::{
+    calculate_flex, FlexBalance, FlexDay, FlexMonthEnd, FlexPeriod, FlexRules, ReportedHours,
+};
+pub use hours::Hours;
+pub use overtime::{calculate_overtime, OvertimePeriod, OvertimeReport, WorkedTime};
+pub use period_calendar::{CalendarPeriod, PeriodCalendar, PeriodFormat};
+pub use period_id::PeriodId;
+pub use progress::Progress;
+pub use reconcile::{PeriodReconciliation, Reconciliation, ReportingStatus};
+pub use relative::resolve_relative_date;
+pub use report::{
+    MonthReport, PeriodLookup, PeriodReport, PeriodSummary, WorkHoursReport, YearReport,
+};
+pub use schedule::{EmploymentRate, Rounding, SchedulePlan, ScheduleSegment, WorkSchedule};
+pub use scheme::{MergeDirection, PeriodScheme, UbwRules};
+pub use vacation::{
+    vacation_balance, RequestedVacation, VacationBalance, VacationRules, MIN_DAYS_TAKEN,
+    STATUTORY_PAID_DAYS,
+};
+
 /// Extends NaiveDate with additional functionality
 trait NaiveDateExt {
     /// Returns the number of days in the month for this date
@@ -42,6 +97,50 @@ impl NaiveDateExt for chrono::NaiveDate {
     }
 }
 
+/// Options controlling how work hours are calculated
+///
+/// # Example
+///
+/// ```
+/// use time_tally::workhours::{holidays::FinnishCalendar, CalculationOptions, WorkSchedule};
+///
+/// let options = CalculationOptions {
+///     calendar: Box::new(FinnishCalendar),
+///     schedule: "8,8,8,8,0,0,0".parse::<WorkSchedule>().unwrap().into(),
+///     ..Default::default()
+/// };
+/// ```
+pub struct CalculationOptions {
+    /// The holiday calendar to exclude holidays and reduced days from
+    pub calendar: Box<dyn HolidayCalendar + Send + Sync>,
+    /// The normal work time on each day of the week, with any date-ranged overrides
+    pub schedule: SchedulePlan,
+    /// The employment rate scaling the work time of each day
+    pub employment_rate: EmploymentRate,
+    /// How the scaled work time of each day is rounded
+    pub rounding: Rounding,
+    /// How the calculated range is split into reporting periods
+    pub scheme: PeriodScheme,
+    /// The date splitting the work hours into elapsed and remaining hours, if any
+    pub as_of: Option<NaiveDate>,
+    /// Leave deducted from the work hours, in order of precedence where absences overlap
+    pub absences: Vec<Absence>,
+}
+
+impl Default for CalculationOptions {
+    fn default() -> Self {
+        CalculationOptions {
+            calendar: Box::new(SwedishCalendar::default()),
+            schedule: SchedulePlan::default(),
+            employment_rate: EmploymentRate::default(),
+            rounding: Rounding::default(),
+            scheme: PeriodScheme::default(),
+            as_of: None,
+            absences: Vec::new(),
+        }
+    }
+}
+
 /// Represents the calculated work hours for a given time range
 #[derive(Serialize, Debug)]
 pub struct WorkHours {
@@ -49,7 +148,13 @@ pub struct WorkHours {
     #[serde(flatten)]
     years: BTreeMap<String, Year>,
     /// Total work hours across all periods
-    total: u32,
+    total: Hours,
+    /// Elapsed and remaining work hours across all periods, if calculated as of a date
+    #[serde(flatten)]
+    progress: Option<Progress>,
+    /// Hours of absence by type across all periods, if any absences were given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    absence: Option<AbsenceHours>,
 }
 
 /// Represents work hours for a specific year
@@ -59,164 +164,736 @@ struct Year {
     #[serde(flatten)]
     months: BTreeMap<String, Month>,
     /// Total work hours for the year
-    total: u32,
+    total: Hours,
+    /// Elapsed and remaining work hours for the year, if calculated as of a date
+    #[serde(flatten)]
+    progress: Option<Progress>,
+    /// Hours of absence by type for the year, if any absences were given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    absence: Option<AbsenceHours>,
 }
 
 /// Represents work hours for a specific month
 #[derive(Serialize, Debug)]
 struct Month {
-    /// Work periods in the month with their work hours
+    /// Work periods in the month by id with their work hours, or with their details
     #[serde(flatten)]
-    weeks: BTreeMap<String, u32>,
+    weeks: BTreeMap<PeriodId, PeriodEntry>,
     /// Total work hours for the month
-    total: u32,
+    total: Hours,
+    /// Elapsed and remaining work hours for the month, if calculated as of a date
+    #[serde(flatten)]
+    progress: Option<Progress>,
+    /// Hours of absence by type for the month, if any absences were given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    absence: Option<AbsenceHours>,
+}
+
+/// The value of a period in a `Month`
+#[derive(Serialize, Debug)]
+#[serde(untagged)]
+enum PeriodEntry {
+    /// The work hours of the period only
+    Hours(Hours),
+    /// The dates, working days and work hours of the period
+    Detail(Period),
+}
+
+/// A reporting period, or the part of one within a calculated date range
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Period {
+    /// The unique identifier of the period, e.g. "2024-06-W24"
+    pub id: PeriodId,
+    /// The name of the period, e.g. "week: 23"
+    #[serde(skip)]
+    pub name: String,
+    /// The position of the period among the periods starting in its month, starting at 1
+    #[serde(skip)]
+    pub sequence: u32,
+    /// The first date of the period within the calculated range
+    pub start: NaiveDate,
+    /// The last date of the period within the calculated range
+    pub end: NaiveDate,
+    /// The first date of the whole period
+    pub period_start: NaiveDate,
+    /// The last date of the whole period
+    pub period_end: NaiveDate,
+    /// The ISO week of the period: its first week if it starts on a Monday and spans more
+    /// than a week, otherwise the week of its last day
+    pub iso_week: u32,
+    /// The number of days with work hours within the calculated range
+    pub working_days: u32,
+    /// The work hours within the calculated range
+    pub hours: Hours,
+    /// The elapsed and remaining work hours within the calculated range, if calculated as of
+    /// a date
+    #[serde(flatten)]
+    pub progress: Option<Progress>,
+    /// The hours of absence by type within the calculated range, which are not included in
+    /// `hours`, if any absences were given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub absence: Option<AbsenceHours>,
+    /// Every day of the period within the calculated range with its work hours
+    #[serde(skip)]
+    pub days: Vec<PeriodDay>,
+}
+
+/// A day of a reporting period with its work hours
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeriodDay {
+    /// The date of the day
+    pub date: NaiveDate,
+    /// The work hours of the day, net of absence
+    pub hours: Hours,
+}
+
+impl WorkHours {
+    /// Groups periods by year and month
+    ///
+    /// # Arguments
+    ///
+    /// * `periods` - The periods in date order
+    /// * `detail` - Whether to include the dates and working days of each period, or only its hours
+    fn from_periods(periods: Vec<Period>, detail: bool) -> Self {
+        let mut years: BTreeMap<String, Year> = BTreeMap::new();
+        let mut total_workhours = Hours::ZERO;
+        let mut total_progress = None;
+        let mut total_absence = None;
+
+        for period in periods {
+            //Make keys
+            let year = period.start.year().to_string();
+            let month = format!("{:02}-{}", period.start.month(), period.start.format("%B"));
+
+            //check if year is in years and add it if not
+            let year_entry = years.entry(year).or_insert_with(|| Year {
+                months: BTreeMap::new(),
+                total: Hours::ZERO,
+                progress: None,
+                absence: None,
+            });
+
+            //check if month is in year.months and add it if not
+            let month_entry = year_entry.months.entry(month).or_insert_with(|| Month {
+                weeks: BTreeMap::new(),
+                total: Hours::ZERO,
+                progress: None,
+                absence: None,
+            });
+
+            //Aggregate workhour sums
+            let workhours = period.hours;
+            total_workhours += workhours;
+            year_entry.total += workhours;
+            month_entry.total += workhours;
+            progress::accumulate(&mut total_progress, period.progress);
+            progress::accumulate(&mut year_entry.progress, period.progress);
+            progress::accumulate(&mut month_entry.progress, period.progress);
+            absence::accumulate(&mut total_absence, period.absence.as_ref());
+            absence::accumulate(&mut year_entry.absence, period.absence.as_ref());
+            absence::accumulate(&mut month_entry.absence, period.absence.as_ref());
+
+            //Add current week to year.month
+            let entry = if detail {
+                PeriodEntry::Detail(period.clone())
+            } else {
+                PeriodEntry::Hours(workhours)
+            };
+            month_entry.weeks.insert(period.id, entry);
+        }
+
+        WorkHours {
+            years,
+            total: total_workhours,
+            progress: total_progress,
+            absence: total_absence,
+        }
+    }
 }
 
 /// Calculates work hours for a period between two dates (inclusive)
 ///
 /// # Arguments
 ///
-/// * `start` - Start date in the format "DD-MM-YYYY"
-/// * `end` - End date in the format "DD-MM-YYYY"
+/// * `start` - Start date in the format "DD-MM-YYYY", "YYYY-MM-DD" or "YYYYMMDD", or an ISO
+///   week "YYYY-Www" starting on its Monday
+/// * `end` - End date in the same formats, with an ISO week ending on its Sunday
+/// * `options` - The holiday calendar, work schedule and employment rate to calculate with
 ///
 /// # Returns
 ///
-/// A `Result` containing a `WorkHours` struct if successful, or an error message if not
+/// A `Result` containing a `WorkHours` struct if successful, or a `WorkhoursError` if not
 ///
 /// # Errors
 ///
 /// Returns an error if:
-/// - The date strings are not in the correct format
-/// - The start date is after the end date
+/// - The date strings are not in any of the accepted formats, or are ambiguous
+/// - The start date is after the end date or the range spans more than `MAX_RANGE_DAYS` days
+/// - There's an issue calculating holidays or period boundaries
 ///
 /// # Example
 ///
 /// ```
-/// use time_tally::workhours::calculate_workhours;
+/// use time_tally::workhours::{calculate_workhours, CalculationOptions};
 /// use axum::response::Json;
 ///
-/// let work_hours = Json(calculate_workhours("01-01-2024".to_string(), "31-12-2024".to_string()).unwrap());
+/// let work_hours = Json(
+///     calculate_workhours("01-01-2024".to_string(), "31-12-2024".to_string(), &CalculationOptions::default()).unwrap(),
+/// );
 /// println!("Total work hours in 2024: {:#?}", work_hours);
 /// ```
-pub fn calculate_workhours(start: String, end: String) -> Result<WorkHours, String> {
+pub fn calculate_workhours(
+    start: String,
+    end: String,
+    options: &CalculationOptions,
+) -> Result<WorkHours, WorkhoursError> {
+    let periods = calculate_periods(start, end, options)?;
+    Ok(WorkHours::from_periods(periods, false))
+}
+
+/// Calculates work hours for a period between two dates (inclusive), including the
+/// start and end dates, working days and ISO week of each reporting period
+///
+/// Takes the same arguments and returns the same errors as `calculate_workhours`.
+pub fn calculate_workhours_detailed(
+    start: String,
+    end: String,
+    options: &CalculationOptions,
+) -> Result<WorkHours, WorkhoursError> {
+    let periods = calculate_periods(start, end, options)?;
+    Ok(WorkHours::from_periods(periods, true))
+}
+
+/// Calculates the reporting periods between two dates (inclusive)
+///
+/// The first and last periods are clipped to the given dates.
+///
+/// # Arguments
+///
+/// * `start` - Start date in any of the formats accepted by `calculate_workhours`
+/// * `end` - End date in any of the formats accepted by `calculate_workhours`
+/// * `options` - The holiday calendar, work schedule and employment rate to calculate with
+///
+/// # Returns
+///
+/// A `Result` containing the periods in date order, or a `WorkhoursError` if not
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The date strings are not in any of the accepted formats, or are ambiguous
+/// - The start date is after the end date or the range spans more than `MAX_RANGE_DAYS` days
+/// - There's an issue calculating holidays or period boundaries
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use time_tally::workhours::{calculate_periods, CalculationOptions, Hours};
+///
+/// let periods = calculate_periods("29-05-2024".to_string(), "09-06-2024".to_string(), &CalculationOptions::default()).unwrap();
+/// assert_eq!(periods[0].name, "week: 22");
+/// assert_eq!(periods[0].end, NaiveDate::from_ymd_opt(2024, 5, 31).unwrap());
+/// assert_eq!(periods[0].working_days, 3);
+/// assert_eq!(periods[1].period_start, NaiveDate::from_ymd_opt(2024, 6, 1).unwrap());
+/// assert_eq!(periods[1].hours, Hours::from_hours(32));
+/// ```
+pub fn calculate_periods(
+    start: String,
+    end: String,
+    options: &CalculationOptions,
+) -> Result<Vec<Period>, WorkhoursError> {
     //Convert to dates
     let (start_date, end_date) = parse_dates(start, end)?;
+    periods_between(&start_date, &end_date, options)
+}
+
+/// Calculates work hours for a period between two dates (inclusive) as a `WorkHoursReport`
+/// of ordered years, months and periods
+///
+/// Takes the same arguments and returns the same errors as `calculate_workhours`.
+///
+/// # Example
+///
+/// ```
+/// use time_tally::workhours::{calculate_report, CalculationOptions, Hours};
+///
+/// let report = calculate_report("01-01-2024".to_string(), "31-12-2024".to_string(), &CalculationOptions::default()).unwrap();
+/// assert_eq!(report.years[0].months[5].name, "June");
+/// assert_eq!(report.years[0].months[5].periods[0].index, 1);
+/// assert_eq!(report.years[0].months[5].total, Hours::from_hours(8 * 18));
+/// ```
+pub fn calculate_report(
+    start: String,
+    end: String,
+    options: &CalculationOptions,
+) -> Result<WorkHoursReport, WorkhoursError> {
+    let (start_date, end_date) = parse_dates(start, end)?;
+    let periods = periods_between(&start_date, &end_date, options)?;
+    Ok(WorkHoursReport::from_periods(start_date, end_date, periods))
+}
 
-    let mut years: BTreeMap<String, Year> = BTreeMap::new();
-    let mut total_workhours = 0;
+/// Compares hours reported, e.g. in UBW, against the expected work hours of each reporting
+/// period between two dates (inclusive)
+///
+/// # Arguments
+///
+/// * `start` - Start date in any of the formats accepted by `calculate_workhours`
+/// * `end` - End date in any of the formats accepted by `calculate_workhours`
+/// * `reported` - Hours reported by period id, e.g. "2024-06-W23", or by date in the formats
+///   accepted by `calculate_workhours` except ISO weeks. Hours of dates are added to the
+///   period containing the date.
+/// * `options` - The holiday calendar, work schedule and employment rate to calculate with
+///
+/// # Returns
+///
+/// A `Result` containing the expected and reported hours and their difference for each
+/// period and for the whole range, or a `WorkhoursError` if not
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The date strings are not in any of the accepted formats, or are ambiguous
+/// - The start date is after the end date or the range spans more than `MAX_RANGE_DAYS` days
+/// - A key of the reported hours is neither a period id nor a date, is a date outside the
+///   range or a period not wholly inside it, or has negative hours
+/// - The keys of a date or period add up to more than 24 hours per day, or hours are
+///   reported for both a period and a date within it
+/// - There's an issue calculating holidays or period boundaries
+///
+/// # Example
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use time_tally::workhours::{reconcile_hours, CalculationOptions, Hours, ReportingStatus};
+///
+/// let reported = BTreeMap::from([("2024-06-W23".to_string(), Hours::from_hours(40))]);
+/// let reconciliation = reconcile_hours("01-06-2024".to_string(), "09-06-2024".to_string(), &reported, &CalculationOptions::default()).unwrap();
+/// assert_eq!(reconciliation.expected, Hours::from_hours(32));
+/// assert_eq!(reconciliation.periods[0].difference, Hours::from_hours(8));
+/// assert_eq!(reconciliation.periods[0].status, ReportingStatus::Over);
+/// ```
+pub fn reconcile_hours(
+    start: String,
+    end: String,
+    reported: &BTreeMap<String, Hours>,
+    options: &CalculationOptions,
+) -> Result<Reconciliation, WorkhoursError> {
+    let (start_date, end_date) = parse_dates(start, end)?;
+    let periods = periods_between(&start_date, &end_date, options)?;
+    Reconciliation::from_periods(start_date, end_date, periods, reported)
+}
 
-    let mut current_date = start_date;
-    while current_date <= end_date {
-        //Make keys
-        let year = current_date.year().to_string();
-        let month = format!("{:02}-{}", current_date.month(), current_date.format("%B"));
+/// Lists all reporting periods in a year with their work hours
+///
+/// # Arguments
+///
+/// * `year` - The year to list the periods of
+/// * `options` - The holiday calendar, work schedule and employment rate to calculate with
+///
+/// # Returns
+///
+/// A `Result` containing the numbered periods of the year, or a `WorkhoursError` if not
+///
+/// # Errors
+///
+/// Returns an error if the year is out of range or there's an issue calculating holidays
+///
+/// # Example
+///
+/// ```
+/// use time_tally::workhours::{period_calendar, CalculationOptions, PeriodFormat};
+///
+/// let calendar = period_calendar(2025, &CalculationOptions::default()).unwrap();
+/// assert_eq!(calendar.periods[0].name, "week: 1");
+/// println!("{}", calendar.render(PeriodFormat::Csv));
+/// ```
+pub fn period_calendar(
+    year: i32,
+    options: &CalculationOptions,
+) -> Result<PeriodCalendar, WorkhoursError> {
+    let (start_date, end_date) = year_range(year)?;
+    let periods = periods_between(&start_date, &end_date, options)?;
+    Ok(PeriodCalendar::from_periods(year, periods))
+}
 
-        //Calculate workhours in current week
-        let (week, workhours, period_end_date) = calculate_period(&current_date, &end_date)?;
+/// Finds the reporting period containing a date, together with the periods before and after it
+///
+/// # Arguments
+///
+/// * `date` - A date in any of the formats accepted by `calculate_workhours`
+/// * `options` - The holiday calendar, work schedule and employment rate to calculate with
+///
+/// # Returns
+///
+/// A `Result` containing the whole periods with their work hours, or a `WorkhoursError` if not
+///
+/// # Errors
+///
+/// Returns an error if the date string is not in any of the accepted formats, or is ambiguous
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use time_tally::workhours::{lookup_period, CalculationOptions};
+///
+/// let lookup = lookup_period("05-06-2024".to_string(), &CalculationOptions::default()).unwrap();
+/// assert_eq!(lookup.period.name, "week: 23");
+/// assert_eq!(lookup.period.period.period_end, NaiveDate::from_ymd_opt(2024, 6, 9).unwrap());
+/// assert_eq!(lookup.previous.name, "week: 22");
+/// assert_eq!(lookup.next.name, "week: 24");
+/// ```
+pub fn lookup_period(
+    date: String,
+    options: &CalculationOptions,
+) -> Result<PeriodLookup, WorkhoursError> {
+    let date = parse_date(&date, DateBound::Start)?;
+
+    let period = whole_period(&date, options)?;
+    let previous = whole_period(&(period.period_start - Duration::days(1)), options)?;
+    let next = whole_period(&(period.period_end + Duration::days(1)), options)?;
+    Ok(PeriodLookup {
+        period: period.into(),
+        previous: previous.into(),
+        next: next.into(),
+    })
+}
 
-        //check if year is in years and add it if not
-        let year_entry = years.entry(year).or_insert_with(|| Year {
-            months: BTreeMap::new(),
-            total: 0,
-        });
+/// Calculates the whole reporting period containing a date
+fn whole_period(date: &NaiveDate, options: &CalculationOptions) -> Result<Period, WorkhoursError> {
+    let (period_start, period_end) = options
+        .scheme
+        .boundaries(date)
+        .map_err(WorkhoursError::Calendar)?;
+    calculate_period(&period_start, &period_end, options)
+}
 
-        //check if month is in year.months and add it if not
-        let month_entry = year_entry.months.entry(month).or_insert_with(|| Month {
-            weeks: BTreeMap::new(),
-            total: 0,
-        });
+/// Calculates the reporting periods between two parsed dates (inclusive)
+fn periods_between(
+    start_date: &NaiveDate,
+    end_date: &NaiveDate,
+    options: &CalculationOptions,
+) -> Result<Vec<Period>, WorkhoursError> {
+    let mut periods = Vec::new();
+    let mut current_date = *start_date;
+    while current_date <= *end_date {
+        //Calculate workhours in current week
+        let period = calculate_period(&current_date, end_date, options)?;
+        current_date = period.end + Duration::days(1);
+        periods.push(period);
+    }
+    Ok(periods)
+}
 
-        //Add current week to year.month
-        month_entry.weeks.insert(week, workhours);
+/// Returns the first and last days of a month
+///
+/// # Arguments
+///
+/// * `month` - A month in the format "YYYY-MM"
+///
+/// # Errors
+///
+/// Returns an error if the month is not in the correct format or outside `SUPPORTED_YEARS`
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use time_tally::workhours::month_range;
+///
+/// let (start, end) = month_range("2024-02").unwrap();
+/// assert_eq!(start, NaiveDate::from_ymd_opt(2024, 2, 1).unwrap());
+/// assert_eq!(end, NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+/// ```
+pub fn month_range(month: &str) -> Result<(NaiveDate, NaiveDate), WorkhoursError> {
+    parse_month(month)
+        .filter(|(first, _)| SUPPORTED_YEARS.contains(&first.year()))
+        .ok_or_else(|| WorkhoursError::InvalidMonth(month.to_string()))
+}
 
-        //Aggregate workhour sums
-        total_workhours += workhours;
-        year_entry.total += workhours;
-        month_entry.total += workhours;
+/// Returns the first and last days of a year
+///
+/// # Errors
+///
+/// Returns an error if the year is outside `SUPPORTED_YEARS`
+pub fn year_range(year: i32) -> Result<(NaiveDate, NaiveDate), WorkhoursError> {
+    year_bounds(year)
+        .filter(|_| SUPPORTED_YEARS.contains(&year))
+        .ok_or(WorkhoursError::InvalidYear(year))
+}
 
-        current_date = period_end_date + Duration::days(1);
+/// Returns the first and last days of a whole reporting period
+///
+/// # Arguments
+///
+/// * `period` - The `PeriodId` of the period, the month it starts in and its ISO week, e.g.
+///   "2024-03-W10"
+/// * `options` - The period scheme and calendar to find the period with
+///
+/// # Errors
+///
+/// Returns an error if the period is not a period id or no period of the scheme starting in
+/// the month matches it
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use time_tally::workhours::{period_range, CalculationOptions};
+///
+/// let (start, end) = period_range("2024-06-W23", &CalculationOptions::default()).unwrap();
+/// assert_eq!(start, NaiveDate::from_ymd_opt(2024, 6, 1).unwrap());
+/// assert_eq!(end, NaiveDate::from_ymd_opt(2024, 6, 9).unwrap());
+/// ```
+pub fn period_range(
+    period: &str,
+    options: &CalculationOptions,
+) -> Result<(NaiveDate, NaiveDate), WorkhoursError> {
+    let invalid = || WorkhoursError::InvalidPeriod(period.to_string());
+    let id: PeriodId = period.parse().map_err(|_| invalid())?;
+    let (first, last) =
+        parse_month(&format!("{:04}-{:02}", id.year, id.month)).ok_or_else(invalid)?;
+
+    let mut candidate = whole_period(&first, options)?;
+    //A period containing the first of the month may start in the month before
+    if candidate.period_start < first {
+        candidate = whole_period(&(candidate.period_end + Duration::days(1)), options)?;
+    }
+    while candidate.period_start <= last {
+        if candidate.id == id {
+            return Ok((candidate.period_start, candidate.period_end));
+        }
+        candidate = whole_period(&(candidate.period_end + Duration::days(1)), options)?;
     }
+    Err(invalid())
+}
 
-    //Return
-    Ok(WorkHours {
-        years,
-        total: total_workhours,
-    })
+/// Lists the holidays between two dates (inclusive)
+///
+/// # Arguments
+///
+/// * `start` - Start date in any of the formats accepted by `calculate_workhours`
+/// * `end` - End date in any of the formats accepted by `calculate_workhours`
+/// * `calendar` - The holiday calendar to list holidays from
+///
+/// # Returns
+///
+/// A `Result` containing the holidays in the range sorted by date, or a `WorkhoursError` if not
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The date strings are not in any of the accepted formats, or are ambiguous
+/// - The start date is after the end date or the range spans more than `MAX_RANGE_DAYS` days
+/// - There's an issue calculating holidays
+///
+/// # Example
+///
+/// ```
+/// use time_tally::workhours::{holidays::SwedishCalendar, list_holidays};
+///
+/// let holidays = list_holidays("01-06-2024".to_string(), "30-06-2024".to_string(), &SwedishCalendar::default()).unwrap();
+/// assert_eq!(holidays[0].name, "Sveriges nationaldag");
+/// ```
+pub fn list_holidays(
+    start: String,
+    end: String,
+    calendar: &dyn HolidayCalendar,
+) -> Result<Vec<Holiday>, WorkhoursError> {
+    let (start_date, end_date) = parse_dates(start, end)?;
+
+    let mut holidays: Vec<Holiday> = calendar
+        .for_years(start_date.year(), end_date.year())
+        .map_err(WorkhoursError::Calendar)?
+        .into_iter()
+        .filter(|holiday| holiday.date >= start_date && holiday.date <= end_date)
+        .collect();
+    holidays.sort_by_key(|holiday| holiday.date);
+    Ok(holidays)
 }
 
 /// Parses date strings into NaiveDate objects
 ///
 /// # Arguments
 ///
-/// * `start` - Start date string in the format "DD-MM-YYYY"
-/// * `end` - End date string in the format "DD-MM-YYYY"
+/// * `start` - Start date string in any of the formats accepted by `calculate_workhours`
+/// * `end` - End date string in any of the formats accepted by `calculate_workhours`
 ///
 /// # Returns
 ///
-/// A `Result` containing a tuple of `(NaiveDate, NaiveDate)` if successful, or an error message if not
+/// A `Result` containing a tuple of `(NaiveDate, NaiveDate)` if successful, or a `WorkhoursError` if not
 ///
 /// # Errors
 ///
 /// Returns an error if:
-/// - The date strings are not in the correct format
+/// - The date strings are not in any of the accepted formats, or are ambiguous
+/// - A date is outside `SUPPORTED_YEARS`
 /// - The start date is after the end date
-fn parse_dates(start: String, end: String) -> Result<(NaiveDate, NaiveDate), String> {
-    // TODO: include more date format checks from the explore project
-    let start_date =
-        NaiveDate::parse_from_str(&start, "%d-%m-%Y").map_err(|_| "Invalid start date")?;
-
-    let end_date = NaiveDate::parse_from_str(&end, "%d-%m-%Y").map_err(|_| "Invalid start date")?;
+/// - The range spans more than `MAX_RANGE_DAYS` days
+fn parse_dates(start: String, end: String) -> Result<(NaiveDate, NaiveDate), WorkhoursError> {
+    let start_date = parse_named_date(
+        start,
+        DateBound::Start,
+        "start",
+        WorkhoursError::InvalidStart,
+    )?;
+    let end_date = parse_named_date(end, DateBound::End, "end", WorkhoursError::InvalidEnd)?;
 
     if start_date > end_date {
-        return Err("Start date must be before end date".to_string());
+        return Err(WorkhoursError::ReversedRange {
+            start: start_date,
+            end: end_date,
+        });
+    }
+    let days = (end_date - start_date).num_days() + 1;
+    if days > MAX_RANGE_DAYS {
+        return Err(WorkhoursError::RangeTooLarge { days });
     }
     Ok((start_date, end_date))
 }
 
+/// Parses a date in any of the formats accepted by `calculate_workhours`
+///
+/// # Arguments
+///
+/// * `date` - The date, e.g. "05-06-2024", "2024-06-05", "20240605" or "2024-W23"
+/// * `bound` - Whether an ISO week resolves to its Monday, as a start, or its Sunday, as an end
+///
+/// # Errors
+///
+/// Returns an error if the date is not in any of the accepted formats, or is ambiguous
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use time_tally::workhours::{parse_date, DateBound};
+///
+/// assert_eq!(parse_date("2024-W23", DateBound::End), Ok(NaiveDate::from_ymd_opt(2024, 6, 9).unwrap()));
+/// ```
+pub fn parse_date(date: &str, bound: DateBound) -> Result<NaiveDate, WorkhoursError> {
+    parse_named_date(date.to_string(), bound, "date", WorkhoursError::InvalidDate)
+}
+
+/// Parses a single date string, reporting errors for the named input
+fn parse_named_date(
+    input: String,
+    bound: DateBound,
+    parameter: &'static str,
+    invalid: fn(String) -> WorkhoursError,
+) -> Result<NaiveDate, WorkhoursError> {
+    match parse_date_input(&input, bound) {
+        Ok(date) if SUPPORTED_YEARS.contains(&date.year()) => Ok(date),
+        Ok(_) | Err(DateInputError::Invalid) => Err(invalid(input)),
+        Err(DateInputError::Ambiguous) => Err(WorkhoursError::AmbiguousDate { parameter, input }),
+    }
+}
+
 /// Calculates work hours for a specific period
 ///
 /// # Arguments
 ///
 /// * `start_date` - The start date of the period
+/// * `end_date` - The last date to include, if it falls before the end of the period
+/// * `options` - The holiday calendar, work schedule and employment rate to calculate with
 ///
 /// # Returns
 ///
-/// A `Result` containing a tuple of `(String, u32, NaiveDate)` representing:
-/// - The period name (e.g., "week: 23")
-/// - The number of work hours in the period
-/// - The end date of the period
+/// A `Result` containing the period within the range with its work hours, in total and for
+/// each of its days
 ///
 /// # Errors
 ///
-/// Returns an error if there's an issue calculating holidays
+/// Returns an error if there's an issue calculating holidays or period boundaries
 fn calculate_period(
     start_date: &NaiveDate,
     end_date: &NaiveDate,
-) -> Result<(String, u32, NaiveDate), String> {
-    let mut hours = 0;
+    options: &CalculationOptions,
+) -> Result<Period, WorkhoursError> {
+    let mut hours = Hours::ZERO;
+    let mut elapsed = Hours::ZERO;
+    let mut absence_hours = AbsenceHours::default();
+    let mut working_days = 0;
+    let mut days = Vec::new();
     let mut date = *start_date;
 
-    let (period_start, period_end) = period_boundaries(start_date)?;
-    let period_name = period_name(&period_start, &period_end);
-    let holidays = holidays::for_years(start_date.year(), period_end.year())?;
-
-    let period_end = *cmp::min(end_date, &period_end);
-    while date <= period_end {
-        hours += if date.weekday() == chrono::Weekday::Sat
-            || date.weekday() == chrono::Weekday::Sun
-            || holidays.contains(&date)
-        {
-            0
-        } else {
-            8
-        };
+    let (period_start, period_end) = options
+        .scheme
+        .boundaries(start_date)
+        .map_err(WorkhoursError::Calendar)?;
+    let holidays = options
+        .calendar
+        .for_years(start_date.year(), period_end.year())
+        .map_err(WorkhoursError::Calendar)?;
+
+    let end = *cmp::min(end_date, &period_end);
+    while date <= end {
+        let day_hours = scheduled_hours(date, &holidays, options);
+        let day_hours = absence::deduct(&options.absences, date, day_hours, &mut absence_hours);
+        if day_hours > Hours::ZERO {
+            working_days += 1;
+        }
+        hours += day_hours;
+        if options.as_of.is_some_and(|as_of| date < as_of) {
+            elapsed += day_hours;
+        }
+        days.push(PeriodDay {
+            date,
+            hours: day_hours,
+        });
 
         date += Duration::days(1);
     }
-    Ok((period_name, hours, period_end))
+    let iso_week = period_week(&period_start, &period_end);
+    Ok(Period {
+        id: PeriodId {
+            year: period_start.year(),
+            month: period_start.month(),
+            week: iso_week,
+        },
+        sequence: options
+            .scheme
+            .sequence_in_month(&period_start)
+            .map_err(WorkhoursError::Calendar)?,
+        name: options.scheme.name(&period_start, &period_end),
+        start: *start_date,
+        end,
+        period_start,
+        period_end,
+        iso_week,
+        working_days,
+        hours,
+        progress: options.as_of.map(|_| Progress {
+            elapsed,
+            remaining: hours - elapsed,
+        }),
+        absence: (!options.absences.is_empty()).then_some(absence_hours),
+        days,
+    })
 }
 
-/// Determines the boundaries of a reporting period for a given date
+/// Calculates the work hours of a single day before any absences
+///
+/// # Arguments
+///
+/// * `date` - The day
+/// * `holidays` - The holidays of the calendar in the years around the day
+/// * `options` - The work schedule and employment rate to calculate with
+fn scheduled_hours(date: NaiveDate, holidays: &[Holiday], options: &CalculationOptions) -> Hours {
+    let normal_hours = options.schedule.hours_on(date);
+
+    //Holidays and reduced days can only lower the hours of the day
+    let day_hours = holidays
+        .iter()
+        .filter(|holiday| holiday.date == date)
+        .map(|holiday| holiday.kind.work_hours(normal_hours))
+        .min()
+        .unwrap_or(normal_hours);
+    options.employment_rate.scale(day_hours, options.rounding)
+}
+
+/// Determines the boundaries of a reporting period for a given date with the default UBW rules
 ///
 /// # Arguments
 ///
@@ -229,41 +906,9 @@ fn calculate_period(
 /// # Errors
 ///
 /// Returns an error if the calculation results in an invalid date
+#[cfg(test)]
 fn period_boundaries(date: &NaiveDate) -> Result<(NaiveDate, NaiveDate), String> {
-    let latest_monday = (date.day() as i8) - (date.weekday().num_days_from_monday() as i8);
-    let last_week_len = (date.days_in_month() as i8) - latest_monday;
-
-    let (start_date, len) = match latest_monday {
-        -5..=-2 => (
-            NaiveDate::from_ymd_opt(date.year(), date.month(), 1).ok_or("Invalid start date")?,
-            latest_monday + 13,
-        ),
-        -1..=5 => (
-            NaiveDate::from_ymd_opt(date.year(), date.month(), 1).ok_or("Invalid start date")?,
-            latest_monday + 6,
-        ),
-        6..=31 => {
-            let new_day = if last_week_len > 1 {
-                date.day() - date.weekday().num_days_from_monday()
-            } else {
-                date.day() - date.weekday().num_days_from_monday() - 7
-            };
-            let new_date = NaiveDate::from_ymd_opt(date.year(), date.month(), new_day)
-                .ok_or("Invalid calculated date")?;
-
-            let len = match last_week_len {
-                0 | 1 => last_week_len + 8,
-                2..=8 => last_week_len + 1,
-                9..=31 => 7,
-                _ => return Err("Resulting calc should never be outside 0..=31.".into()),
-            };
-            (new_date, len)
-        }
-        _ => return Err("Resulting calc should never be outside -5..=31.".into()),
-    };
-    let end_date = start_date + Duration::days(len as i64 - 1);
-
-    Ok((start_date, end_date))
+    UbwRules::default().boundaries(date)
 }
 
 /// Generates a name for a reporting period
@@ -277,113 +922,357 @@ fn period_boundaries(date: &NaiveDate) -> Result<(NaiveDate, NaiveDate), String>
 ///
 /// A `String` representing the period name (e.g., "week: 23")
 fn period_name(start: &NaiveDate, end: &NaiveDate) -> String {
+    format!("week: {}", period_week(start, end))
+}
+
+/// Determines the ISO week a reporting period is named after
+///
+/// Periods extended past a full week are named after their first week, all others after
+/// the week of their last day.
+fn period_week(start: &NaiveDate, end: &NaiveDate) -> u32 {
     let len = (*end - *start).num_days();
     if start.weekday() == chrono::Weekday::Mon && len >= 7 {
-        format!("week: {}", start.iso_week().week())
+        start.iso_week().week()
     } else {
-        format!("week: {}", end.iso_week().week())
+        end.iso_week().week()
     }
 }
 
-/// Module for handling Swedish holidays
-mod holidays {
-    use chrono::{Datelike, Duration, NaiveDate};
-    /// Calculates holidays for a range of years
-    ///
-    /// # Arguments
-    ///
-    /// * `start_year` - The first year to calculate holidays for
-    /// * `end_year` - The last year to calculate holidays for
-    ///
-    /// # Returns
-    ///
-    /// A `Result` containing a `Vec<NaiveDate>` of all holidays in the given range, or an error message
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if there's an issue calculating holidays for any year in the range
-    pub fn for_years(start_year: i32, end_year: i32) -> Result<Vec<NaiveDate>, String> {
-        let mut holidays: Vec<NaiveDate> = Vec::new();
-        for year in start_year..=end_year {
-            let year_holidays = get_year_holidays(year)?;
-            holidays.extend(year_holidays.iter().cloned());
+#[cfg(test)]
+mod workhours_tests {
+    use super::*;
+    use holidays::SwedishEve;
+
+    fn swedish_with_eves(calendar: SwedishCalendar) -> CalculationOptions {
+        CalculationOptions {
+            calendar: Box::new(calendar),
+            ..Default::default()
         }
-        Ok(holidays)
     }
 
-    ///Returns a list of fixed holiday days in Sweden
-    /// Gets holidays for a specific year
-    ///
-    /// # Arguments
-    ///
-    /// * `year` - The year to calculate holidays for
-    ///
-    /// # Returns
-    ///
-    /// A `Result` containing an array of 12 `NaiveDate` objects representing the holidays for the year, or an error message
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if there's an issue calculating any of the holidays
-    fn get_year_holidays(year: i32) -> Result<[NaiveDate; 12], String> {
-        let fixed_dates = [
-            NaiveDate::from_ymd_opt(year, 1, 1).ok_or("Failed to initiate fixed date")?,
-            NaiveDate::from_ymd_opt(year, 1, 6).ok_or("Failed to initiate fixed date")?,
-            NaiveDate::from_ymd_opt(year, 5, 1).ok_or("Failed to initiate fixed date")?,
-            NaiveDate::from_ymd_opt(year, 12, 24).ok_or("Failed to initiate fixed date")?,
-            NaiveDate::from_ymd_opt(year, 12, 25).ok_or("Failed to initiate fixed date")?,
-            NaiveDate::from_ymd_opt(year, 12, 26).ok_or("Failed to initiate fixed date")?,
-            NaiveDate::from_ymd_opt(year, 12, 31).ok_or("Failed to initiate fixed date")?,
-        ];
-        let easter_dates = easter(year)?;
-
-        Ok([
-            fixed_dates[0],
-            fixed_dates[1],
-            fixed_dates[2],
-            fixed_dates[3],
-            fixed_dates[4],
-            fixed_dates[5],
-            fixed_dates[6],
-            easter_dates[0],
-            easter_dates[1],
-            easter_dates[2],
-            midsummer(year)?,
-            national_day(year)?,
-        ])
-    }
-
-    ///Returns the friday before easter, monday after easter and ascension date.
-    fn easter(year: i32) -> Result<[NaiveDate; 3], String> {
-        let easter = computus::gregorian_naive(year)?;
-        Ok([
-            easter - Duration::days(2),  //Långfredag
-            easter + Duration::days(1),  //Annandag
-            easter + Duration::days(40), //Kristihimmelsfärd
-        ])
-    }
-
-    ///Returns the Swedish naitonal day if it is not on a weekend. Otherwise, returns the friday before.
-    fn national_day(year: i32) -> Result<NaiveDate, String> {
-        let national_day =
-            NaiveDate::from_ymd_opt(year, 6, 6).ok_or("Failed to calculate the national day")?;
-        match national_day.weekday() {
-            chrono::Weekday::Sat => Ok(national_day - Duration::days(1)),
-            chrono::Weekday::Sun => Ok(national_day - Duration::days(2)),
-            _ => Ok(national_day),
+    //22-30 apr 2024: 7 weekdays, valborgsmässoafton on tue 30 apr
+    #[test]
+    fn reduced_eve_lowers_period_hours() {
+        let end_date = NaiveDate::from_ymd_opt(2024, 4, 30).unwrap();
+        let start_date = NaiveDate::from_ymd_opt(2024, 4, 22).unwrap();
+
+        let period =
+            calculate_period(&start_date, &end_date, &CalculationOptions::default()).unwrap();
+        assert_eq!(period.hours, Hours::from_hours(56));
+        assert_eq!(period.working_days, 7);
+
+        let options = swedish_with_eves(
+            SwedishCalendar::default().with_reduced_eve(SwedishEve::Valborgsmassoafton, 4),
+        );
+        let period = calculate_period(&start_date, &end_date, &options).unwrap();
+        assert_eq!(period.hours, Hours::from_hours(52));
+    }
+
+    //25-31 mar 2024: skärtorsdag on thu 28 mar and långfredag on fri 29 mar
+    #[test]
+    fn reduced_eve_next_to_holidays() {
+        let options = swedish_with_eves(SwedishCalendar::default().with_reduced_eves(6));
+        let work_hours =
+            calculate_workhours("25-03-2024".to_string(), "31-03-2024".to_string(), &options)
+                .unwrap();
+        assert_eq!(work_hours.total, Hours::from_hours(8 * 3 + 6));
+    }
+
+    //1-14 apr 2024: annandag påsk on mon 1 apr, a 7:30 friday and a 4 hour saturday
+    #[test]
+    fn work_schedule_sets_daily_hours() {
+        let options = CalculationOptions {
+            schedule: "8,8,8,8,7:30,4,0".parse::<WorkSchedule>().unwrap().into(),
+            ..Default::default()
+        };
+        let work_hours =
+            calculate_workhours("01-04-2024".to_string(), "14-04-2024".to_string(), &options)
+                .unwrap();
+        assert_eq!(
+            work_hours.total,
+            Hours::from_minutes(2 * (4 * 480 + 450 + 240) - 480)
+        );
+    }
+
+    //1-7 apr 2024: annandag påsk on mon 1 apr, 7:45 scaled per day at 80% is 6:12
+    #[test]
+    fn employment_rate_scales_each_day() {
+        let options = CalculationOptions {
+            schedule: "7:45,7:45,7:45,7:45,7:45,0,0"
+                .parse::<WorkSchedule>()
+                .unwrap()
+                .into(),
+            employment_rate: EmploymentRate::new(80.0).unwrap(),
+            rounding: Rounding::Quarter,
+            ..Default::default()
+        };
+        let work_hours =
+            calculate_workhours("01-04-2024".to_string(), "07-04-2024".to_string(), &options)
+                .unwrap();
+        assert_eq!(work_hours.total, Hours::from_minutes(375 * 4));
+
+        let options = CalculationOptions {
+            rounding: Rounding::Minute,
+            ..options
+        };
+        let work_hours =
+            calculate_workhours("01-04-2024".to_string(), "07-04-2024".to_string(), &options)
+                .unwrap();
+        assert_eq!(work_hours.total, Hours::from_minutes(372 * 4));
+    }
+
+    //5-13 jun 2024: nationaldagen on thu 6 jun, sat 1 jun in week 23, both periods clipped
+    #[test]
+    fn periods_are_clipped_to_range() {
+        let date = |day| NaiveDate::from_ymd_opt(2024, 6, day).unwrap();
+        let periods = calculate_periods(
+            "05-06-2024".to_string(),
+            "13-06-2024".to_string(),
+            &CalculationOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(periods.len(), 2);
+        assert_eq!((periods[0].start, periods[0].end), (date(5), date(9)));
+        assert_eq!(
+            (periods[0].period_start, periods[0].period_end),
+            (date(1), date(9))
+        );
+        assert_eq!(periods[0].iso_week, 23);
+        assert_eq!(periods[0].working_days, 2);
+        assert_eq!((periods[1].start, periods[1].end), (date(10), date(13)));
+        assert_eq!(periods[1].period_end, date(16));
+        assert_eq!(periods[1].working_days, 4);
+        assert_eq!(
+            periods.iter().map(|period| period.hours).sum::<Hours>(),
+            Hours::from_hours(48)
+        );
+    }
+
+    //30 dec 2024-5 jan 2025: nyårsafton on tue 31 dec, nyårsdagen on wed 1 jan
+    #[test]
+    fn report_is_ordered_across_years() {
+        let report = calculate_report(
+            "30-12-2024".to_string(),
+            "05-01-2025".to_string(),
+            &CalculationOptions::default(),
+        )
+        .unwrap();
+
+        let years: Vec<i32> = report.years.iter().map(|year| year.year).collect();
+        assert_eq!(years, vec![2024, 2025]);
+        assert_eq!(report.years[0].months[0].month, 12);
+        assert_eq!(report.years[0].total, Hours::from_hours(8));
+        assert_eq!(report.years[1].months[0].name, "January");
+        assert_eq!(report.years[1].months[0].periods[0].index, 1);
+        assert_eq!(report.years[1].total, Hours::from_hours(8 * 2));
+        assert_eq!(report.total, Hours::from_hours(8 * 3));
+    }
+
+    //12-20 jun 2024: the periods of weeks 24 and 25 are the second and third of june, after 1-9 jun
+    #[test]
+    fn report_index_is_the_sequence_within_the_month() {
+        let report = calculate_report(
+            "12-06-2024".to_string(),
+            "20-06-2024".to_string(),
+            &CalculationOptions::default(),
+        )
+        .unwrap();
+
+        let indexes: Vec<u32> = report.years[0].months[0]
+            .periods
+            .iter()
+            .map(|period| period.index)
+            .collect();
+        assert_eq!(indexes, vec![2, 3]);
+    }
+
+    //1 feb-31 mar 2024 bi-weekly from mon 22 jan: the periods of 22 jan-4 feb and 19 feb-3 mar
+    //cross month breaks, february has 21 weekdays and march has 21 with långfredag on fri 29 mar
+    #[test]
+    fn periods_crossing_a_month_are_split() {
+        let options = CalculationOptions {
+            scheme: PeriodScheme::BiWeekly(NaiveDate::from_ymd_opt(2024, 1, 22)),
+            ..Default::default()
+        };
+        let work_hours =
+            calculate_workhours("01-02-2024".to_string(), "31-03-2024".to_string(), &options)
+                .unwrap();
+
+        let february = &work_hours.years["2024"].months["02-February"];
+        assert_eq!(february.total, Hours::from_hours(8 * 21));
+        let ids: Vec<String> = february.weeks.keys().map(PeriodId::to_string).collect();
+        assert_eq!(ids, vec!["2024-02-W05", "2024-02-W06", "2024-02-W08"]);
+        assert_eq!(
+            work_hours.years["2024"].months["03-March"].total,
+            Hours::from_hours(8 * 20)
+        );
+
+        let report =
+            calculate_report("01-02-2024".to_string(), "31-03-2024".to_string(), &options).unwrap();
+        assert_eq!(report.years[0].months[0].total, Hours::from_hours(8 * 21));
+        assert_eq!(report.years[0].months[1].periods[0].period.start.day(), 1);
+    }
+
+    //as of wed 12 jun 2024: 6 working days in june before it, as nationaldagen on thu 6 jun is off
+    #[test]
+    fn progress_as_of_a_date() {
+        let options = CalculationOptions {
+            as_of: NaiveDate::from_ymd_opt(2024, 6, 12),
+            ..Default::default()
+        };
+        let work_hours =
+            calculate_workhours("27-05-2024".to_string(), "16-06-2024".to_string(), &options)
+                .unwrap();
+
+        assert_eq!(
+            work_hours.progress,
+            Some(Progress {
+                elapsed: Hours::from_hours(8 * (5 + 6)),
+                remaining: Hours::from_hours(8 * 3),
+            })
+        );
+        let june = &work_hours.years["2024"].months["06-June"];
+        assert_eq!(
+            june.progress,
+            Some(Progress {
+                elapsed: Hours::from_hours(8 * 6),
+                remaining: Hours::from_hours(8 * 3),
+            })
+        );
+        assert_eq!(
+            work_hours.years["2024"].months["05-May"]
+                .progress
+                .map(|progress| progress.remaining),
+            Some(Hours::ZERO)
+        );
+
+        let without = calculate_workhours(
+            "27-05-2024".to_string(),
+            "16-06-2024".to_string(),
+            &CalculationOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(without.progress, None);
+    }
+
+    //1-3 mar 2024 are merged into week 10, so no period of march is named after week 9
+    #[test]
+    fn period_range_by_id() {
+        let options = CalculationOptions::default();
+        let date = |day| NaiveDate::from_ymd_opt(2024, 3, day).unwrap();
+
+        assert_eq!(
+            period_range("2024-03-W10", &options),
+            Ok((date(1), date(10)))
+        );
+        assert_eq!(
+            period_range("2024-03-W11", &options),
+            Ok((date(11), date(17)))
+        );
+        for invalid in ["2024-03-W09", "2024-03-2-W11", "2024-3-W10", "2024-03-W1"] {
+            assert_eq!(
+                period_range(invalid, &options),
+                Err(WorkhoursError::InvalidPeriod(invalid.to_string()))
+            );
         }
     }
 
-    ///Calculates date of Swedish midsummer given a year.
-    fn midsummer(year: i32) -> Result<NaiveDate, String> {
-        let mut date = NaiveDate::from_ymd_opt(year, 6, 30)
-            .ok_or("Failed when initiating midsummer date calculation")?;
-        while date.weekday().num_days_from_monday() != 4 {
-            date = date
-                .pred_opt()
-                .ok_or("Failed when stepping dates towards midsummer")?;
+    //2 jan 2024: week 1 runs from nyårsdagen on mon 1 jan, after week 52 from mon 25 dec 2023
+    #[test]
+    fn lookup_period_across_years() {
+        let lookup =
+            lookup_period("02-01-2024".to_string(), &CalculationOptions::default()).unwrap();
+
+        assert_eq!((lookup.period.year, lookup.period.month), (2024, 1));
+        assert_eq!(
+            lookup.period.period.period_start,
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()
+        );
+        assert_eq!(lookup.period.period.hours, Hours::from_hours(8 * 4));
+        assert_eq!((lookup.previous.year, lookup.previous.month), (2023, 12));
+        assert_eq!(
+            lookup.previous.period.period_start,
+            NaiveDate::from_ymd_opt(2023, 12, 25).unwrap()
+        );
+        assert_eq!(lookup.next.period.working_days, 5);
+    }
+
+    #[test]
+    fn parse_dates_reports_the_invalid_date() {
+        let parse = |start: &str, end: &str| parse_dates(start.to_string(), end.to_string());
+
+        assert_eq!(
+            parse("32-01-2024", "31-01-2024"),
+            Err(WorkhoursError::InvalidStart("32-01-2024".to_string()))
+        );
+        assert_eq!(
+            parse("01-01-2024", "31-13-2024"),
+            Err(WorkhoursError::InvalidEnd("31-13-2024".to_string()))
+        );
+        assert_eq!(
+            parse("01/03/2024", "31-03-2024").map_err(|err| err.parameter()),
+            Err(Some("start"))
+        );
+        assert_eq!(
+            parse("2024-W10", "2024-W10"),
+            parse("04-03-2024", "20240310")
+        );
+        assert_eq!(
+            parse("31-01-2024", "01-01-2024").map_err(|err| err.code()),
+            Err("reversed_range")
+        );
+        assert_eq!(
+            parse("01-01-1900", "31-12-2100"),
+            Err(WorkhoursError::RangeTooLarge { days: 73414 })
+        );
+
+        assert_eq!(
+            parse("01-01-1500", "02-01-1500"),
+            Err(WorkhoursError::InvalidStart("01-01-1500".to_string()))
+        );
+        assert_eq!(
+            parse("01-01-9999", "01-01-10000"),
+            Err(WorkhoursError::InvalidEnd("01-01-10000".to_string()))
+        );
+    }
+
+    //the first and last days of the supported years have holidays in every calendar
+    #[test]
+    fn supported_years_can_be_calculated() {
+        for country in ["SE", "NO", "DK", "FI"] {
+            let options = CalculationOptions {
+                calendar: country.parse::<holidays::Country>().unwrap().calendar(),
+                ..Default::default()
+            };
+            for (start, end) in [("01-01-1583", "31-01-1583"), ("01-12-9999", "31-12-9999")] {
+                assert!(
+                    calculate_workhours(start.to_string(), end.to_string(), &options).is_ok(),
+                    "{} {}",
+                    country,
+                    start
+                );
+            }
         }
-        Ok(date)
+    }
+
+    //24 jun-7 jul 2024: 7 hour summer days from wed 26 jun, in the middle of week 26
+    #[test]
+    fn schedule_segment_splits_period() {
+        let options = CalculationOptions {
+            schedule: SchedulePlan::default()
+                .with_segment(
+                    NaiveDate::from_ymd_opt(2024, 6, 26).unwrap(),
+                    NaiveDate::from_ymd_opt(2024, 8, 15).unwrap(),
+                    WorkSchedule::from_hours([7, 7, 7, 7, 7, 0, 0]),
+                )
+                .unwrap(),
+            ..Default::default()
+        };
+        let work_hours =
+            calculate_workhours("24-06-2024".to_string(), "07-07-2024".to_string(), &options)
+                .unwrap();
+        assert_eq!(work_hours.total, Hours::from_hours(8 * 2 + 7 * 3 + 7 * 5));
     }
 }
 
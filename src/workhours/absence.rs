@@ -0,0 +1,269 @@
+//! # Absences
+//!
+//! This module defines `Absence`, a date range of leave such as vacation ("semester"),
+//! sick leave ("sjukfrånvaro"), care of a sick child ("VAB") or parental leave
+//! ("föräldraledighet"), which is deducted from the expected work hours, and
+//! `AbsenceHours`, the deducted hours broken down by type.
+//!
+//! ## Format
+//!
+//! An absence is written as `FROM..TO:TYPE`, or `DATE:TYPE` for a single day, where the
+//! dates are in any of the formats accepted for the start and end of a range and `TYPE` is
+//! `vacation`, `sick_leave`, `vab` or `parental_leave`. A partial day is written with the
+//! hours absent on each day, either as hours or as hours and minutes, e.g.
+//! `2024-06-10:vab:4` or `2024-W28..2024-W30:parental_leave:1:30`.
+
+use super::date_input::{parse_date_input, DateInputError};
+use super::schedule::parse_day;
+use super::{DateBound, Hours};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ops::AddAssign;
+use std::str::FromStr;
+
+/// A type of leave
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AbsenceKind {
+    /// Vacation ("semester")
+    Vacation,
+    /// Sick leave ("sjukfrånvaro")
+    SickLeave,
+    /// Care of a sick child ("vård av barn")
+    Vab,
+    /// Parental leave ("föräldraledighet")
+    ParentalLeave,
+}
+
+impl FromStr for AbsenceKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "vacation" => Ok(AbsenceKind::Vacation),
+            "sick_leave" => Ok(AbsenceKind::SickLeave),
+            "vab" => Ok(AbsenceKind::Vab),
+            "parental_leave" => Ok(AbsenceKind::ParentalLeave),
+            _ => Err(format!(
+                "Unknown absence type '{}': expected vacation, sick_leave, vab or parental_leave",
+                s
+            )),
+        }
+    }
+}
+
+/// Leave between two dates (inclusive), deducted from the work hours of each day
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use time_tally::workhours::{Absence, AbsenceKind, Hours};
+///
+/// let absence: Absence = "2024-06-10..2024-06-12:vab:4".parse().unwrap();
+/// assert_eq!(absence.kind, AbsenceKind::Vab);
+/// assert_eq!(absence.to, NaiveDate::from_ymd_opt(2024, 6, 12).unwrap());
+/// assert_eq!(absence.hours, Some(Hours::from_hours(4)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Absence {
+    /// The first day of the absence
+    pub from: NaiveDate,
+    /// The last day of the absence
+    pub to: NaiveDate,
+    /// The type of leave
+    pub kind: AbsenceKind,
+    /// The hours absent on each day, or `None` for whole days
+    pub hours: Option<Hours>,
+}
+
+impl Absence {
+    /// Returns the hours of a day that the absence deducts
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The day
+    /// * `day_hours` - The work hours of the day left after any earlier absences
+    fn deduction(&self, date: NaiveDate, day_hours: Hours) -> Hours {
+        if date < self.from || self.to < date {
+            return Hours::ZERO;
+        }
+        self.hours.map_or(day_hours, |hours| hours.min(day_hours))
+    }
+}
+
+impl FromStr for Absence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid absence '{}': expected FROM..TO:TYPE or DATE:TYPE, optionally followed by :HOURS",
+                s
+            )
+        };
+        let mut parts = s.trim().splitn(3, ':');
+        let range = parts.next().ok_or_else(invalid)?;
+        let kind = parts.next().ok_or_else(invalid)?.parse()?;
+        let hours = parts
+            .next()
+            .map(|hours| {
+                parse_day(hours.trim())
+                    .map(|minutes| Hours::from_minutes(minutes as i64))
+                    .ok_or_else(|| format!("Invalid absence '{}': invalid hours '{}'", s, hours))
+            })
+            .transpose()?;
+
+        let (from, to) = range.split_once("..").unwrap_or((range, range));
+        let parse = |date: &str, bound| {
+            parse_date_input(date, bound).map_err(|err| match err {
+                DateInputError::Invalid => invalid(),
+                DateInputError::Ambiguous => {
+                    format!("Invalid absence '{}': ambiguous date '{}'", s, date.trim())
+                }
+            })
+        };
+        let (from, to) = (parse(from, DateBound::Start)?, parse(to, DateBound::End)?);
+        if from > to {
+            return Err(format!(
+                "Invalid absence '{}': {} is after {}",
+                s,
+                from.format("%d-%m-%Y"),
+                to.format("%d-%m-%Y")
+            ));
+        }
+        Ok(Absence {
+            from,
+            to,
+            kind,
+            hours,
+        })
+    }
+}
+
+/// Hours of absence by type of leave
+///
+/// Serializes as an object from type to hours, e.g. `{"vacation": 24, "vab": 4}`.
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct AbsenceHours(BTreeMap<AbsenceKind, Hours>);
+
+impl AbsenceHours {
+    /// Returns the hours of absence of a type
+    pub fn of(&self, kind: AbsenceKind) -> Hours {
+        self.0.get(&kind).copied().unwrap_or_default()
+    }
+
+    /// Returns the hours of absence of all types
+    pub fn total(&self) -> Hours {
+        self.0.values().copied().sum()
+    }
+
+    /// Adds hours of absence of a type
+    fn add(&mut self, kind: AbsenceKind, hours: Hours) {
+        if hours > Hours::ZERO {
+            *self.0.entry(kind).or_default() += hours;
+        }
+    }
+}
+
+impl AddAssign<&AbsenceHours> for AbsenceHours {
+    fn add_assign(&mut self, other: &AbsenceHours) {
+        for (kind, hours) in &other.0 {
+            self.add(*kind, *hours);
+        }
+    }
+}
+
+/// Deducts the absences on a day from its work hours
+///
+/// Overlapping absences are deducted in order, so that a day is never deducted more than
+/// its work hours.
+///
+/// # Returns
+///
+/// The work hours left on the day
+pub(super) fn deduct(
+    absences: &[Absence],
+    date: NaiveDate,
+    day_hours: Hours,
+    absence_hours: &mut AbsenceHours,
+) -> Hours {
+    let mut left = day_hours;
+    for absence in absences {
+        let deduction = absence.deduction(date, left);
+        absence_hours.add(absence.kind, deduction);
+        left -= deduction;
+    }
+    left
+}
+
+/// Adds the absence hours of a part, if any, to the absence hours of the whole it belongs to
+pub(super) fn accumulate(total: &mut Option<AbsenceHours>, part: Option<&AbsenceHours>) {
+    if let Some(part) = part {
+        *total.get_or_insert_with(AbsenceHours::default) += part;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn parses_absences() {
+        let absence: Absence = "2024-W28..2024-W30:Parental_Leave:1:30".parse().unwrap();
+        assert_eq!(
+            absence,
+            Absence {
+                from: date(2024, 7, 8),
+                to: date(2024, 7, 28),
+                kind: AbsenceKind::ParentalLeave,
+                hours: Some(Hours::from_minutes(90)),
+            }
+        );
+
+        let absence: Absence = "05-06-2024:sick_leave".parse().unwrap();
+        assert_eq!(
+            (absence.from, absence.to),
+            (date(2024, 6, 5), date(2024, 6, 5))
+        );
+        assert_eq!(absence.hours, None);
+
+        for invalid in [
+            "2024-06-05",
+            "2024-06-05:holiday",
+            "2024-06-05:vab:25",
+            "2024-06-07..2024-06-05:vab",
+            "05/06/2024:vab",
+        ] {
+            assert!(invalid.parse::<Absence>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn overlapping_absences_never_exceed_the_day() {
+        let absences: Vec<Absence> = ["2024-06-05:vab:3", "2024-06-03..2024-06-07:vacation"]
+            .iter()
+            .map(|absence| absence.parse().unwrap())
+            .collect();
+        let mut absence_hours = AbsenceHours::default();
+
+        let left = deduct(
+            &absences,
+            date(2024, 6, 5),
+            Hours::from_hours(8),
+            &mut absence_hours,
+        );
+        assert_eq!(left, Hours::ZERO);
+        assert_eq!(absence_hours.of(AbsenceKind::Vab), Hours::from_hours(3));
+        assert_eq!(
+            absence_hours.of(AbsenceKind::Vacation),
+            Hours::from_hours(5)
+        );
+        assert_eq!(absence_hours.total(), Hours::from_hours(8));
+    }
+}
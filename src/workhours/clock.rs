@@ -0,0 +1,37 @@
+//! # Clock
+//!
+//! This module defines `Clock`, the source of today's date for relative date expressions
+//! such as "today" or "end-of-month".
+
+use chrono::{NaiveDate, Utc};
+use chrono_tz::Europe::Stockholm;
+
+/// The source of today's date
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use time_tally::workhours::Clock;
+///
+/// let date = NaiveDate::from_ymd_opt(2024, 6, 5).unwrap();
+/// assert_eq!(Clock::Fixed(date).today(), date);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Clock {
+    /// The system clock, with the date as it is in Europe/Stockholm
+    #[default]
+    System,
+    /// A fixed date, e.g. in tests
+    Fixed(NaiveDate),
+}
+
+impl Clock {
+    /// Returns today's date
+    pub fn today(&self) -> NaiveDate {
+        match self {
+            Clock::System => Utc::now().with_timezone(&Stockholm).date_naive(),
+            Clock::Fixed(date) => *date,
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! # Date Input
+//!
+//! This module parses the dates accepted as the start and end of a range:
+//!
+//! - `DD-MM-YYYY`, e.g. `01-03-2024`
+//! - ISO 8601 `YYYY-MM-DD`, e.g. `2024-03-01`
+//! - Compact ISO 8601 `YYYYMMDD`, e.g. `20240301`
+//! - ISO week `YYYY-Www`, e.g. `2024-W10`, which is the Monday of the week as a start and the
+//!   Sunday as an end, or `YYYY-Www-D` for a day of the week, e.g. `2024-W10-5` for Friday
+//!
+//! Dates with slashes, such as `03/01/2024`, and dates with two-digit years, such as
+//! `01-03-24`, are rejected as ambiguous.
+
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+
+/// Whether a date is the start or the end of a range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateBound {
+    /// The first day of the range
+    Start,
+    /// The last day of the range
+    End,
+}
+
+/// Why a date could not be parsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DateInputError {
+    /// The input is not a date in any of the accepted formats
+    Invalid,
+    /// The input could be read as more than one date
+    Ambiguous,
+}
+
+/// Parses a date in any of the accepted formats
+///
+/// # Arguments
+///
+/// * `input` - The date, e.g. "01-03-2024", "2024-03-01", "20240301" or "2024-W10"
+/// * `bound` - Whether the date starts or ends a range, which decides the day a week resolves to
+///
+/// # Returns
+///
+/// A `Result` containing the date, or whether the input is invalid or ambiguous
+pub(crate) fn parse_date_input(input: &str, bound: DateBound) -> Result<NaiveDate, DateInputError> {
+    let input = input.trim();
+    if input.contains('/') {
+        return Err(DateInputError::Ambiguous);
+    }
+    if input.len() == 8 && is_number(input) {
+        return NaiveDate::parse_from_str(input, "%Y%m%d").map_err(|_| DateInputError::Invalid);
+    }
+
+    let parts: Vec<&str> = input.split('-').collect();
+    match parts[..] {
+        [year, week] if week.starts_with('W') => parse_week(year, week, None, bound),
+        [year, week, day] if week.starts_with('W') => parse_week(year, week, Some(day), bound),
+        [first, month, last] if [first, month, last].iter().all(|part| is_number(part)) => {
+            match (first.len(), month.len(), last.len()) {
+                (4, 1..=2, 1..=2) => parse_ymd(first, month, last),
+                (1..=2, 1..=2, 4) => parse_ymd(last, month, first),
+                (1..=2, 1..=2, 1..=2) => Err(DateInputError::Ambiguous),
+                _ => Err(DateInputError::Invalid),
+            }
+        }
+        _ => Err(DateInputError::Invalid),
+    }
+}
+
+/// Parses a single day in any of the accepted formats except ISO weeks
+///
+/// Used for inputs keyed by day, such as reported hours, where a week would silently count
+/// as its Monday.
+///
+/// # Arguments
+///
+/// * `input` - The day, e.g. "01-03-2024", "2024-03-01" or "20240301"
+///
+/// # Returns
+///
+/// A `Result` containing the date, or whether the input is invalid or ambiguous
+pub(crate) fn parse_single_day(input: &str) -> Result<NaiveDate, DateInputError> {
+    if input.contains(['W', 'w']) {
+        return Err(DateInputError::Invalid);
+    }
+    parse_date_input(input, DateBound::Start)
+}
+
+/// Parses a month in the format "YYYY-MM" into its first and last days
+pub(crate) fn parse_month(input: &str) -> Option<(NaiveDate, NaiveDate)> {
+    let (year, month) = input.trim().split_once('-')?;
+    if year.len() != 4 || month.len() != 2 || !is_number(year) || !is_number(month) {
+        return None;
+    }
+    let first = NaiveDate::from_ymd_opt(year.parse().ok()?, month.parse().ok()?, 1)?;
+    let next = first.checked_add_months(Months::new(1))?;
+    Some((first, next - Duration::days(1)))
+}
+
+/// Returns the first and last days of a year
+pub(crate) fn year_bounds(year: i32) -> Option<(NaiveDate, NaiveDate)> {
+    Some((
+        NaiveDate::from_ymd_opt(year, 1, 1)?,
+        NaiveDate::from_ymd_opt(year, 12, 31)?,
+    ))
+}
+
+/// Parses an ISO week such as "2024-W10", with an optional day of the week from 1 (Monday)
+/// to 7 (Sunday)
+fn parse_week(
+    year: &str,
+    week: &str,
+    day: Option<&str>,
+    bound: DateBound,
+) -> Result<NaiveDate, DateInputError> {
+    let week = &week[1..];
+    if year.len() != 4 || week.len() != 2 || !is_number(year) || !is_number(week) {
+        return Err(DateInputError::Invalid);
+    }
+    let weekday = match (day, bound) {
+        (None, DateBound::Start) => Weekday::Mon,
+        (None, DateBound::End) => Weekday::Sun,
+        (Some(day), _) if day.len() == 1 && is_number(day) => day
+            .parse::<u8>()
+            .ok()
+            .and_then(|day| Weekday::try_from(day.wrapping_sub(1)).ok())
+            .ok_or(DateInputError::Invalid)?,
+        (Some(_), _) => return Err(DateInputError::Invalid),
+    };
+    let year = year.parse().map_err(|_| DateInputError::Invalid)?;
+    let week = week.parse().map_err(|_| DateInputError::Invalid)?;
+    NaiveDate::from_isoywd_opt(year, week, weekday)
+        .filter(|date| date.iso_week().week() == week)
+        .ok_or(DateInputError::Invalid)
+}
+
+/// Builds a date from its year, month and day
+fn parse_ymd(year: &str, month: &str, day: &str) -> Result<NaiveDate, DateInputError> {
+    let parse = |part: &str| part.parse::<u32>().map_err(|_| DateInputError::Invalid);
+    NaiveDate::from_ymd_opt(
+        year.parse().map_err(|_| DateInputError::Invalid)?,
+        parse(month)?,
+        parse(day)?,
+    )
+    .ok_or(DateInputError::Invalid)
+}
+
+/// Checks that a string is a non-empty sequence of ASCII digits
+fn is_number(input: &str) -> bool {
+    !input.is_empty() && input.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn accepted_formats() {
+        for input in [
+            "01-03-2024",
+            "1-3-2024",
+            "2024-03-01",
+            "20240301",
+            "2024-W09-5",
+        ] {
+            assert_eq!(
+                parse_date_input(input, DateBound::Start),
+                Ok(date(2024, 3, 1)),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn weeks_resolve_to_the_bound() {
+        assert_eq!(
+            parse_date_input("2024-W10", DateBound::Start),
+            Ok(date(2024, 3, 4))
+        );
+        assert_eq!(
+            parse_date_input("2024-W10", DateBound::End),
+            Ok(date(2024, 3, 10))
+        );
+        //week 1 of 2025 starts in 2024
+        assert_eq!(
+            parse_date_input("2025-W01", DateBound::Start),
+            Ok(date(2024, 12, 30))
+        );
+        assert_eq!(
+            parse_date_input("2024-W53", DateBound::Start),
+            Err(DateInputError::Invalid)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_and_ambiguous_dates() {
+        for input in [
+            "31-02-2024",
+            "2024-3",
+            "2024031",
+            "2024-W10-8",
+            "2024-W1",
+            "March",
+        ] {
+            assert_eq!(
+                parse_date_input(input, DateBound::Start),
+                Err(DateInputError::Invalid),
+                "{}",
+                input
+            );
+        }
+        for input in ["03/01/2024", "2024/03/01", "01-03-24"] {
+            assert_eq!(
+                parse_date_input(input, DateBound::Start),
+                Err(DateInputError::Ambiguous),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn single_days_exclude_weeks() {
+        assert_eq!(parse_single_day("2024-03-01"), Ok(date(2024, 3, 1)));
+        assert_eq!(parse_single_day("01-03-2024"), Ok(date(2024, 3, 1)));
+        for input in ["2024-W09", "2024-W09-5", "2024-w09"] {
+            assert_eq!(
+                parse_single_day(input),
+                Err(DateInputError::Invalid),
+                "{}",
+                input
+            );
+        }
+        assert_eq!(
+            parse_single_day("03/01/2024"),
+            Err(DateInputError::Ambiguous)
+        );
+    }
+
+    #[test]
+    fn months() {
+        assert_eq!(
+            parse_month("2024-02"),
+            Some((date(2024, 2, 1), date(2024, 2, 29)))
+        );
+        assert_eq!(
+            parse_month("2024-12"),
+            Some((date(2024, 12, 1), date(2024, 12, 31)))
+        );
+        assert_eq!(parse_month("2024-13"), None);
+        assert_eq!(parse_month("2024-3"), None);
+        assert_eq!(parse_month("03-2024"), None);
+    }
+}
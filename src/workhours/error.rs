@@ -0,0 +1,220 @@
+//! # Errors
+//!
+//! This module defines `WorkhoursError`, the error returned by the work hours calculations.
+
+use super::Hours;
+use chrono::NaiveDate;
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// The longest range, in days, that work hours can be calculated for in one call
+pub const MAX_RANGE_DAYS: i64 = 100 * 366;
+
+/// The years that work hours can be calculated for, as Easter is only calculated for them
+pub const SUPPORTED_YEARS: RangeInclusive<i32> = 1583..=9999;
+
+/// The accepted date formats, as listed in error messages
+const DATE_FORMATS: &str = "DD-MM-YYYY, YYYY-MM-DD, YYYYMMDD or YYYY-Www";
+
+/// The accepted formats of single days, as listed in error messages
+const DATE_FORMATS_WITHOUT_WEEKS: &str = "DD-MM-YYYY, YYYY-MM-DD or YYYYMMDD";
+
+/// An error from calculating work hours, periods or holidays
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkhoursError {
+    /// The start date is not a valid date in any of the accepted formats, or is outside
+    /// `SUPPORTED_YEARS`
+    InvalidStart(String),
+    /// The end date is not a valid date in any of the accepted formats, or is outside
+    /// `SUPPORTED_YEARS`
+    InvalidEnd(String),
+    /// A single date is not a valid date in any of the accepted formats, or is outside
+    /// `SUPPORTED_YEARS`
+    InvalidDate(String),
+    /// A date could be read as more than one date, e.g. "03/01/2024"
+    AmbiguousDate {
+        /// The name of the input holding the date
+        parameter: &'static str,
+        /// The date as given
+        input: String,
+    },
+    /// The month is not a valid month in the format "YYYY-MM" within `SUPPORTED_YEARS`
+    InvalidMonth(String),
+    /// The year is outside the supported range of dates
+    InvalidYear(i32),
+    /// The period is not a period of the scheme in the format "YYYY-MM-Www"
+    InvalidPeriod(String),
+    /// The start date is after the end date
+    ReversedRange {
+        /// The requested start date
+        start: NaiveDate,
+        /// The requested end date
+        end: NaiveDate,
+    },
+    /// The range spans more than `MAX_RANGE_DAYS` days
+    RangeTooLarge {
+        /// The number of days in the requested range
+        days: i64,
+    },
+    /// A key of the reported hours is neither a date nor a period id
+    InvalidReported(String),
+    /// The hours reported for a key are negative or more than its days can hold
+    InvalidReportedHours {
+        /// The key of the reported hours, a date or a period id
+        key: String,
+        /// The most hours that can be reported for the key
+        max: Hours,
+    },
+    /// Hours were reported for a date outside the range or a period not wholly inside it
+    ReportedOutsideRange(String),
+    /// Hours were reported both for a period and for a date within it
+    ReportedTwice {
+        /// The id of the period
+        period: String,
+        /// The date within the period
+        date: String,
+    },
+    /// Time was worked on a date outside the range
+    WorkedOutsideRange(String),
+    /// Calculating holidays or period boundaries failed
+    Calendar(String),
+}
+
+impl WorkhoursError {
+    /// Returns a stable, machine-readable code for the error, e.g. "invalid_start"
+    pub fn code(&self) -> &'static str {
+        match self {
+            WorkhoursError::InvalidStart(_) => "invalid_start",
+            WorkhoursError::InvalidEnd(_) => "invalid_end",
+            WorkhoursError::InvalidDate(_) => "invalid_date",
+            WorkhoursError::AmbiguousDate { .. } => "ambiguous_date",
+            WorkhoursError::InvalidMonth(_) => "invalid_month",
+            WorkhoursError::InvalidYear(_) => "invalid_year",
+            WorkhoursError::InvalidPeriod(_) => "invalid_period",
+            WorkhoursError::ReversedRange { .. } => "reversed_range",
+            WorkhoursError::RangeTooLarge { .. } => "range_too_large",
+            WorkhoursError::InvalidReported(_) => "invalid_reported",
+            WorkhoursError::InvalidReportedHours { .. } => "invalid_reported_hours",
+            WorkhoursError::ReportedOutsideRange(_) => "reported_outside_range",
+            WorkhoursError::ReportedTwice { .. } => "reported_twice",
+            WorkhoursError::WorkedOutsideRange(_) => "worked_outside_range",
+            WorkhoursError::Calendar(_) => "calendar_failure",
+        }
+    }
+
+    /// Returns the name of the input that caused the error, if any
+    pub fn parameter(&self) -> Option<&'static str> {
+        match self {
+            WorkhoursError::InvalidStart(_) => Some("start"),
+            WorkhoursError::InvalidEnd(_)
+            | WorkhoursError::ReversedRange { .. }
+            | WorkhoursError::RangeTooLarge { .. } => Some("end"),
+            WorkhoursError::InvalidDate(_) => Some("date"),
+            WorkhoursError::AmbiguousDate { parameter, .. } => Some(parameter),
+            WorkhoursError::InvalidMonth(_) => Some("month"),
+            WorkhoursError::InvalidYear(_) => Some("year"),
+            WorkhoursError::InvalidPeriod(_) => Some("period"),
+            WorkhoursError::InvalidReported(_)
+            | WorkhoursError::InvalidReportedHours { .. }
+            | WorkhoursError::ReportedOutsideRange(_)
+            | WorkhoursError::ReportedTwice { .. } => Some("reported"),
+            WorkhoursError::WorkedOutsideRange(_) => Some("worked"),
+            WorkhoursError::Calendar(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for WorkhoursError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkhoursError::InvalidStart(start) => write!(
+                f,
+                "Invalid start date '{}': expected {} from {} to {}",
+                start,
+                DATE_FORMATS,
+                SUPPORTED_YEARS.start(),
+                SUPPORTED_YEARS.end()
+            ),
+            WorkhoursError::InvalidEnd(end) => write!(
+                f,
+                "Invalid end date '{}': expected {} from {} to {}",
+                end,
+                DATE_FORMATS,
+                SUPPORTED_YEARS.start(),
+                SUPPORTED_YEARS.end()
+            ),
+            WorkhoursError::InvalidDate(date) => write!(
+                f,
+                "Invalid date '{}': expected {} from {} to {}",
+                date,
+                DATE_FORMATS,
+                SUPPORTED_YEARS.start(),
+                SUPPORTED_YEARS.end()
+            ),
+            WorkhoursError::AmbiguousDate { parameter, input } => write!(
+                f,
+                "Ambiguous {} date '{}': use {}",
+                parameter, input, DATE_FORMATS
+            ),
+            WorkhoursError::InvalidMonth(month) => write!(
+                f,
+                "Invalid month '{}': expected YYYY-MM from {} to {}",
+                month,
+                SUPPORTED_YEARS.start(),
+                SUPPORTED_YEARS.end()
+            ),
+            WorkhoursError::InvalidYear(year) => write!(
+                f,
+                "Invalid year {}: expected {} to {}",
+                year,
+                SUPPORTED_YEARS.start(),
+                SUPPORTED_YEARS.end()
+            ),
+            WorkhoursError::InvalidPeriod(period) => write!(
+                f,
+                "Invalid period '{}': expected YYYY-MM-Www of a period starting in the month",
+                period
+            ),
+            WorkhoursError::ReversedRange { start, end } => write!(
+                f,
+                "Start date {} must be before end date {}",
+                start.format("%d-%m-%Y"),
+                end.format("%d-%m-%Y")
+            ),
+            WorkhoursError::RangeTooLarge { days } => write!(
+                f,
+                "The range of {} days is larger than the maximum of {} days",
+                days, MAX_RANGE_DAYS
+            ),
+            WorkhoursError::InvalidReported(key) => write!(
+                f,
+                "Invalid reported key '{}': expected a date as {} or a period id YYYY-MM-Www",
+                key, DATE_FORMATS_WITHOUT_WEEKS
+            ),
+            WorkhoursError::InvalidReportedHours { key, max } => write!(
+                f,
+                "Invalid hours reported for '{}': expected 0:00 to {}",
+                key, max
+            ),
+            WorkhoursError::ReportedOutsideRange(key) => write!(
+                f,
+                "Hours are reported for '{}', which is not wholly within the range",
+                key
+            ),
+            WorkhoursError::ReportedTwice { period, date } => write!(
+                f,
+                "Hours are reported both for period '{}' and for its date '{}'",
+                period, date
+            ),
+            WorkhoursError::WorkedOutsideRange(date) => write!(
+                f,
+                "Time is worked on '{}', which is outside the range",
+                date
+            ),
+            WorkhoursError::Calendar(message) => write!(f, "Calendar failure: {}", message),
+        }
+    }
+}
+
+impl Error for WorkhoursError {}
@@ -0,0 +1,380 @@
+//! # Flex Time
+//!
+//! This module keeps the flex-time ("flextid") balance of an employee: the running sum of the
+//! hours reported each day minus the hours expected, by day and by reporting period.
+//!
+//! At the end of each month the balance can be capped, as many flex agreements only allow a
+//! limited surplus, which is forfeited beyond the cap, or deficit, which is settled beyond it,
+//! to carry over into the next month.
+//!
+//! ## Format
+//!
+//! Reported hours are written as semicolon-separated `DATE:HOURS` entries, where the date is
+//! in any of the formats accepted for the start and end of a range except ISO weeks, and the
+//! hours are either hours or hours and minutes, e.g. `2024-06-03:8.5;2024-06-04:7:45`.
+//! Entries for the same date are added up to at most 24 hours.
+
+use super::date_input::{parse_single_day, DateInputError};
+use super::schedule::parse_day;
+use super::{parse_dates, periods_between, CalculationOptions, Hours, PeriodId, WorkhoursError};
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Hours reported by date
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use time_tally::workhours::{Hours, ReportedHours};
+///
+/// let reported: ReportedHours = "2024-06-03:8.5;04-06-2024:7:45".parse().unwrap();
+/// let june_4 = NaiveDate::from_ymd_opt(2024, 6, 4).unwrap();
+/// assert_eq!(reported.on(june_4), Hours::from_minutes(465));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReportedHours(BTreeMap<NaiveDate, Hours>);
+
+impl ReportedHours {
+    /// Returns the hours reported on a date
+    pub fn on(&self, date: NaiveDate) -> Hours {
+        self.0.get(&date).copied().unwrap_or_default()
+    }
+}
+
+impl FromStr for ReportedHours {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut reported = BTreeMap::new();
+        for entry in s.split(';').filter(|entry| !entry.trim().is_empty()) {
+            let invalid = || {
+                format!(
+                    "Invalid reported hours '{}': expected DATE:HOURS, e.g. 2024-06-03:8",
+                    entry
+                )
+            };
+            let (date, hours) = entry.split_once(':').ok_or_else(invalid)?;
+            let date = parse_single_day(date).map_err(|err| match err {
+                DateInputError::Invalid => invalid(),
+                DateInputError::Ambiguous => format!(
+                    "Invalid reported hours '{}': ambiguous date '{}'",
+                    entry,
+                    date.trim()
+                ),
+            })?;
+            let minutes = parse_day(hours.trim()).ok_or_else(invalid)?;
+            let total = reported.entry(date).or_insert(Hours::ZERO);
+            *total += Hours::from_minutes(minutes as i64);
+            if *total > Hours::from_hours(24) {
+                return Err(format!(
+                    "Invalid reported hours for '{}': expected at most {} per day",
+                    date,
+                    Hours::from_hours(24)
+                ));
+            }
+        }
+        Ok(ReportedHours(reported))
+    }
+}
+
+/// How the flex balance carries over from one month to the next
+///
+/// A cap of `None` carries the whole surplus or deficit over.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FlexRules {
+    /// The balance before the first day of the range
+    pub opening_balance: Hours,
+    /// The largest surplus carried over at the end of a month, the rest is forfeited
+    pub max_carry_over: Option<Hours>,
+    /// The largest deficit carried over at the end of a month, the rest is settled, e.g. by
+    /// a salary deduction
+    pub max_deficit: Option<Hours>,
+}
+
+/// The flex balance over a range
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct FlexBalance {
+    /// The first date of the range
+    pub start: NaiveDate,
+    /// The last date of the range
+    pub end: NaiveDate,
+    /// The balance before the first day of the range
+    pub opening_balance: Hours,
+    /// The balance after the last day of the range
+    pub closing_balance: Hours,
+    /// The expected work hours across the range
+    pub expected: Hours,
+    /// The reported hours across the range
+    pub reported: Hours,
+    /// The reporting periods of the range in date order
+    pub periods: Vec<FlexPeriod>,
+    /// The month ends within the range and how their balances carried over
+    pub month_ends: Vec<FlexMonthEnd>,
+    /// Every day of the range in date order
+    pub days: Vec<FlexDay>,
+}
+
+/// The flex time of a single reporting period
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct FlexPeriod {
+    /// The unique identifier of the period, e.g. "2024-06-W23"
+    pub id: PeriodId,
+    /// The name of the period, e.g. "week: 23"
+    pub name: String,
+    /// The first date of the period within the range
+    pub start: NaiveDate,
+    /// The last date of the period within the range
+    pub end: NaiveDate,
+    /// The expected work hours of the period within the range
+    pub expected: Hours,
+    /// The hours reported in the period within the range
+    pub reported: Hours,
+    /// The reported hours minus the expected hours
+    pub difference: Hours,
+    /// The balance after the last day of the period
+    pub balance: Hours,
+}
+
+/// The flex time of a single day
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct FlexDay {
+    /// The day
+    pub date: NaiveDate,
+    /// The expected work hours of the day, net of any absence
+    pub expected: Hours,
+    /// The hours reported on the day
+    pub reported: Hours,
+    /// The balance at the end of the day, after any carry-over at the end of a month
+    pub balance: Hours,
+}
+
+/// How the balance carried over at the end of a month
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct FlexMonthEnd {
+    /// The year of the month
+    pub year: i32,
+    /// The number of the month, 1 for January
+    pub month: u32,
+    /// The balance at the end of the month before any cap
+    pub balance: Hours,
+    /// The balance carried over into the next month
+    pub carried_over: Hours,
+    /// The hours removed by the caps: negative for a forfeited surplus and positive for a
+    /// settled deficit
+    pub adjustment: Hours,
+}
+
+impl FlexRules {
+    /// Caps a balance at the end of a month
+    fn carry_over(&self, balance: Hours) -> Hours {
+        let balance = self.max_carry_over.map_or(balance, |max| balance.min(max));
+        self.max_deficit
+            .map_or(balance, |max| balance.max(Hours::ZERO - max))
+    }
+}
+
+/// Calculates the flex balance over a range
+///
+/// # Arguments
+///
+/// * `start` - Start date in any of the formats accepted by `calculate_workhours`
+/// * `end` - End date in any of the formats accepted by `calculate_workhours`
+/// * `reported` - The hours reported by date
+/// * `rules` - The opening balance and the caps on carry-over at month ends
+/// * `options` - The holiday calendar, work schedule, employment rate and absences deciding
+///   the expected hours
+///
+/// # Returns
+///
+/// A `Result` containing the running balance by day and by period, or a `WorkhoursError` if
+/// not
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The date strings are not in any of the accepted formats, or are ambiguous
+/// - The start date is after the end date or the range spans more than `MAX_RANGE_DAYS` days
+/// - Hours are reported on a date outside the range
+/// - There's an issue calculating holidays or period boundaries
+///
+/// # Example
+///
+/// ```
+/// use time_tally::workhours::{calculate_flex, CalculationOptions, FlexRules, Hours};
+///
+/// let reported = "2024-06-03:9;2024-06-04:9;2024-06-05:7".parse().unwrap();
+/// let flex = calculate_flex("03-06-2024".to_string(), "05-06-2024".to_string(), &reported, &FlexRules::default(), &CalculationOptions::default()).unwrap();
+/// assert_eq!(flex.days[1].balance, Hours::from_hours(2));
+/// assert_eq!(flex.closing_balance, Hours::from_hours(1));
+/// ```
+pub fn calculate_flex(
+    start: String,
+    end: String,
+    reported: &ReportedHours,
+    rules: &FlexRules,
+    options: &CalculationOptions,
+) -> Result<FlexBalance, WorkhoursError> {
+    let (start_date, end_date) = parse_dates(start, end)?;
+    if let Some(date) = reported
+        .0
+        .keys()
+        .find(|date| **date < start_date || end_date < **date)
+    {
+        return Err(WorkhoursError::ReportedOutsideRange(date.to_string()));
+    }
+    let mut balance = rules.opening_balance;
+    let mut periods = Vec::new();
+    let mut month_ends = Vec::new();
+    let mut days = Vec::new();
+    for period in periods_between(&start_date, &end_date, options)? {
+        let mut reported_hours = Hours::ZERO;
+        for day in &period.days {
+            let date = day.date;
+            let day_reported = reported.on(date);
+            reported_hours += day_reported;
+            balance += day_reported - day.hours;
+
+            if (date + Duration::days(1)).month() != date.month() {
+                let carried_over = rules.carry_over(balance);
+                month_ends.push(FlexMonthEnd {
+                    year: date.year(),
+                    month: date.month(),
+                    balance,
+                    carried_over,
+                    adjustment: carried_over - balance,
+                });
+                balance = carried_over;
+            }
+            days.push(FlexDay {
+                date,
+                expected: day.hours,
+                reported: day_reported,
+                balance,
+            });
+        }
+        periods.push(FlexPeriod {
+            id: period.id,
+            name: period.name,
+            start: period.start,
+            end: period.end,
+            expected: period.hours,
+            reported: reported_hours,
+            difference: reported_hours - period.hours,
+            balance,
+        });
+    }
+
+    Ok(FlexBalance {
+        start: start_date,
+        end: end_date,
+        opening_balance: rules.opening_balance,
+        closing_balance: balance,
+        expected: periods.iter().map(|period| period.expected).sum(),
+        reported: periods.iter().map(|period| period.reported).sum(),
+        periods,
+        month_ends,
+        days,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flex(start: &str, end: &str, reported: &str, rules: FlexRules) -> FlexBalance {
+        calculate_flex(
+            start.to_string(),
+            end.to_string(),
+            &reported.parse().unwrap(),
+            &rules,
+            &CalculationOptions::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_reported_hours() {
+        let reported: ReportedHours = "2024-06-03:8; 2024-06-03:0:30;20240604:7.5"
+            .parse()
+            .unwrap();
+        let date = |day| NaiveDate::from_ymd_opt(2024, 6, day).unwrap();
+        assert_eq!(reported.on(date(3)), Hours::from_minutes(510));
+        assert_eq!(reported.on(date(4)), Hours::from_minutes(450));
+        assert_eq!(reported.on(date(5)), Hours::ZERO);
+
+        for invalid in [
+            "2024-06-03",
+            "2024-W23:8",
+            "03/06/2024:8",
+            "2024-06-03:25",
+            "2024-06-03:24;2024-06-03:24",
+            "2024-06-03:20;03-06-2024:4:01",
+        ] {
+            assert!(invalid.parse::<ReportedHours>().is_err(), "{}", invalid);
+        }
+    }
+
+    //Thu 30 may to tue 4 jun 2024, with the month break splitting the UBW periods
+    #[test]
+    fn running_balance_by_day_and_period() {
+        let flex = flex(
+            "2024-05-30",
+            "2024-06-04",
+            "2024-05-30:10;2024-05-31:9;2024-06-01:2;2024-06-03:6;2024-06-04:8",
+            FlexRules {
+                opening_balance: Hours::from_hours(1),
+                ..Default::default()
+            },
+        );
+        let balances: Vec<_> = flex.days.iter().map(|day| day.balance).collect();
+        let hours = Hours::from_hours;
+        assert_eq!(
+            balances,
+            vec![hours(3), hours(4), hours(6), hours(6), hours(4), hours(4)]
+        );
+        assert_eq!(flex.periods.len(), 2);
+        assert_eq!(flex.periods[0].difference, hours(3));
+        assert_eq!(flex.periods[0].balance, hours(4));
+        assert_eq!(flex.periods[1].difference, Hours::ZERO);
+        assert_eq!(flex.closing_balance, hours(4));
+        assert_eq!(flex.month_ends[0].adjustment, Hours::ZERO);
+    }
+
+    #[test]
+    fn month_end_caps() {
+        let rules = FlexRules {
+            max_carry_over: Some(Hours::from_hours(2)),
+            max_deficit: Some(Hours::from_hours(1)),
+            ..Default::default()
+        };
+        let deficit = flex("2024-05-30", "2024-06-03", "2024-05-30:12", rules);
+        assert_eq!(deficit.month_ends[0].balance, Hours::from_hours(4 - 8));
+        assert_eq!(deficit.month_ends[0].carried_over, Hours::from_hours(-1));
+        assert_eq!(deficit.month_ends[0].adjustment, Hours::from_hours(3));
+        assert_eq!(deficit.closing_balance, Hours::from_hours(-9));
+
+        let surplus = flex("2024-05-31", "2024-06-01", "2024-05-31:13", rules);
+        assert_eq!(surplus.month_ends[0].carried_over, Hours::from_hours(2));
+        assert_eq!(surplus.month_ends[0].adjustment, Hours::from_hours(-3));
+    }
+
+    #[test]
+    fn rejects_hours_outside_the_range() {
+        let result = calculate_flex(
+            "2024-06-03".to_string(),
+            "2024-06-07".to_string(),
+            &"2024-06-10:8".parse().unwrap(),
+            &FlexRules::default(),
+            &CalculationOptions::default(),
+        );
+        assert_eq!(
+            result,
+            Err(WorkhoursError::ReportedOutsideRange(
+                "2024-06-10".to_string()
+            ))
+        );
+    }
+}
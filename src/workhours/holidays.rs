@@ -0,0 +1,340 @@
+//! # Holiday Calendars
+//!
+//! This module defines the `HolidayCalendar` trait used by the work hours calculator
+//! to decide which days are holidays, together with the built-in calendars.
+//!
+//! ## Key Concepts
+//!
+//! - **Holiday**: A named date on which no, or fewer than normal, work hours are expected.
+//! - **Holiday Kind**: Whether the day is a public holiday by law, a customary day off or a
+//!   working day with reduced hours, such as an eve shortened by a collective agreement.
+//! - **Holiday Calendar**: A source of holidays for any given year, e.g. the Swedish calendar.
+//! - **Country**: Selects one of the built-in Nordic calendars (SE, NO, DK, FI).
+
+mod denmark;
+mod finland;
+mod norway;
+mod sweden;
+
+use super::Hours;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+pub use denmark::DanishCalendar;
+pub use finland::FinnishCalendar;
+pub use norway::NorwegianCalendar;
+pub use sweden::{SwedishCalendar, SwedishEve};
+
+/// The countries with a built-in holiday calendar
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Country {
+    /// Sweden
+    #[default]
+    #[serde(alias = "se")]
+    Se,
+    /// Norway
+    #[serde(alias = "no")]
+    No,
+    /// Denmark
+    #[serde(alias = "dk")]
+    Dk,
+    /// Finland
+    #[serde(alias = "fi")]
+    Fi,
+}
+
+impl Country {
+    /// Returns the built-in holiday calendar for the country
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use time_tally::workhours::holidays::Country;
+    ///
+    /// let holidays = Country::No.calendar().holidays(2024).unwrap();
+    /// assert!(holidays.iter().any(|holiday| holiday.name == "Grunnlovsdag"));
+    /// ```
+    pub fn calendar(&self) -> Box<dyn HolidayCalendar + Send + Sync> {
+        match self {
+            Country::Se => Box::new(SwedishCalendar::default()),
+            Country::No => Box::new(NorwegianCalendar),
+            Country::Dk => Box::new(DanishCalendar),
+            Country::Fi => Box::new(FinnishCalendar),
+        }
+    }
+}
+
+impl FromStr for Country {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "SE" => Ok(Country::Se),
+            "NO" => Ok(Country::No),
+            "DK" => Ok(Country::Dk),
+            "FI" => Ok(Country::Fi),
+            _ => Err(format!(
+                "Invalid country '{}': expected SE, NO, DK or FI",
+                s
+            )),
+        }
+    }
+}
+
+/// The kind of day a holiday represents
+///
+/// Serializes as the name of the kind only; the hours of a `ReducedHours` day are
+/// available through `HolidayKind::work_hours`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HolidayKind {
+    /// A public holiday by law
+    PublicHoliday,
+    /// Not a public holiday by law, but a day off by custom or agreement, e.g. Christmas Eve
+    CustomaryDayOff,
+    /// A working day shortened to the given number of hours, e.g. an eve ("halvdag")
+    ReducedHours(u32),
+}
+
+impl HolidayKind {
+    /// Returns the name of the kind, e.g. "PublicHoliday"
+    pub fn name(&self) -> &'static str {
+        match self {
+            HolidayKind::PublicHoliday => "PublicHoliday",
+            HolidayKind::CustomaryDayOff => "CustomaryDayOff",
+            HolidayKind::ReducedHours(_) => "ReducedHours",
+        }
+    }
+
+    /// Returns the work hours on a day of this kind
+    ///
+    /// # Arguments
+    ///
+    /// * `normal_hours` - The work hours on the day had it not been a holiday
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use time_tally::workhours::{holidays::HolidayKind, Hours};
+    ///
+    /// let eight = Hours::from_hours(8);
+    /// assert_eq!(HolidayKind::PublicHoliday.work_hours(eight), Hours::ZERO);
+    /// assert_eq!(HolidayKind::ReducedHours(4).work_hours(eight), Hours::from_hours(4));
+    /// assert_eq!(HolidayKind::ReducedHours(4).work_hours(Hours::ZERO), Hours::ZERO);
+    /// ```
+    pub fn work_hours(&self, normal_hours: Hours) -> Hours {
+        match self {
+            HolidayKind::PublicHoliday | HolidayKind::CustomaryDayOff => Hours::ZERO,
+            HolidayKind::ReducedHours(hours) => normal_hours.min(Hours::from_hours(*hours as i64)),
+        }
+    }
+}
+
+impl Serialize for HolidayKind {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+/// Represents a single holiday in a calendar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Holiday {
+    /// The date of the holiday
+    pub date: NaiveDate,
+    /// The name of the holiday in the calendar's own language
+    pub name: String,
+    /// The name of the holiday in English
+    pub english_name: String,
+    /// The kind of holiday
+    pub kind: HolidayKind,
+}
+
+impl Holiday {
+    /// Creates a new holiday
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The date of the holiday
+    /// * `name` - The name of the holiday in the calendar's own language
+    /// * `english_name` - The name of the holiday in English
+    /// * `kind` - The kind of holiday
+    pub fn new(
+        date: NaiveDate,
+        name: impl Into<String>,
+        english_name: impl Into<String>,
+        kind: HolidayKind,
+    ) -> Self {
+        Holiday {
+            date,
+            name: name.into(),
+            english_name: english_name.into(),
+            kind,
+        }
+    }
+
+    /// Returns the name of the holiday in the given language
+    pub fn localized_name(&self, language: Language) -> &str {
+        match language {
+            Language::Local | Language::Sv => &self.name,
+            Language::En => &self.english_name,
+        }
+    }
+}
+
+/// The languages holiday names are available in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    /// The calendar's own language, e.g. Swedish for the Swedish calendar
+    #[default]
+    Local,
+    /// Swedish, only available for the Swedish calendar
+    Sv,
+    /// English
+    En,
+}
+
+/// A source of holidays used when calculating work hours
+///
+/// Implement this trait to calculate work hours against a calendar other than the
+/// built-in ones.
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use time_tally::workhours::holidays::{Holiday, HolidayCalendar, HolidayKind};
+///
+/// struct CompanyCalendar;
+///
+/// impl HolidayCalendar for CompanyCalendar {
+///     fn holidays(&self, year: i32) -> Result<Vec<Holiday>, String> {
+///         let founders_day = NaiveDate::from_ymd_opt(year, 9, 1).ok_or("Invalid year")?;
+///         Ok(vec![Holiday::new(
+///             founders_day,
+///             "Grundardagen",
+///             "Founders' day",
+///             HolidayKind::CustomaryDayOff,
+///         )])
+///     }
+/// }
+///
+/// let holidays = CompanyCalendar.for_years(2024, 2025).unwrap();
+/// assert_eq!(holidays.len(), 2);
+/// ```
+pub trait HolidayCalendar {
+    /// Gets holidays for a specific year
+    ///
+    /// # Arguments
+    ///
+    /// * `year` - The year to calculate holidays for
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a `Vec<Holiday>` of all holidays in the year, or an error message
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there's an issue calculating any of the holidays
+    fn holidays(&self, year: i32) -> Result<Vec<Holiday>, String>;
+
+    /// Calculates holidays for a range of years
+    ///
+    /// # Arguments
+    ///
+    /// * `start_year` - The first year to calculate holidays for
+    /// * `end_year` - The last year to calculate holidays for
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a `Vec<Holiday>` of all holidays in the given range, or an error message
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there's an issue calculating holidays for any year in the range
+    fn for_years(&self, start_year: i32, end_year: i32) -> Result<Vec<Holiday>, String> {
+        let mut holidays: Vec<Holiday> = Vec::new();
+        for year in start_year..=end_year {
+            holidays.extend(self.holidays(year)?);
+        }
+        Ok(holidays)
+    }
+}
+
+/// Calculates the date of Easter Sunday for a given year
+fn easter_sunday(year: i32) -> Result<NaiveDate, String> {
+    Ok(computus::gregorian_naive(year)?)
+}
+
+/// Returns a date relative to Easter Sunday of the given year
+///
+/// # Arguments
+///
+/// * `year` - The year to calculate the date for
+/// * `offset` - The number of days from Easter Sunday, negative for days before
+fn easter_relative(year: i32, offset: i64) -> Result<NaiveDate, String> {
+    Ok(easter_sunday(year)? + Duration::days(offset))
+}
+
+/// Creates a fixed date, e.g. Christmas Day, in the given year
+fn fixed_date(year: i32, month: u32, day: u32) -> Result<NaiveDate, String> {
+    NaiveDate::from_ymd_opt(year, month, day).ok_or_else(|| "Failed to initiate fixed date".into())
+}
+
+/// Finds the first occurrence of a weekday on or after a given date
+///
+/// Used for movable holidays such as midsummer, which falls on the Saturday between 20 and 26 June.
+///
+/// # Arguments
+///
+/// * `year` - The year to search in
+/// * `month` - The month of the earliest possible date
+/// * `day` - The day of the earliest possible date
+/// * `weekday` - The weekday the holiday falls on
+fn first_weekday_from(
+    year: i32,
+    month: u32,
+    day: u32,
+    weekday: Weekday,
+) -> Result<NaiveDate, String> {
+    let date = fixed_date(year, month, day)?;
+    let offset = (7 + weekday.num_days_from_monday() - date.weekday().num_days_from_monday()) % 7;
+    Ok(date + Duration::days(offset as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn easter_2024() {
+        assert_eq!(
+            easter_sunday(2024).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 3, 31).unwrap()
+        );
+    }
+
+    #[test]
+    fn first_weekday_from_finds_saturday_in_window() {
+        for year in 2000..=2100 {
+            let date = first_weekday_from(year, 6, 20, Weekday::Sat).unwrap();
+            assert_eq!(date.weekday(), Weekday::Sat);
+            assert!((20..=26).contains(&date.day()));
+        }
+    }
+
+    #[test]
+    fn parses_countries() {
+        assert_eq!("dk".parse(), Ok(Country::Dk));
+        assert_eq!("FI".parse(), Ok(Country::Fi));
+        assert!("DE".parse::<Country>().is_err());
+    }
+
+    #[test]
+    fn every_country_has_a_calendar() {
+        for country in [Country::Se, Country::No, Country::Dk, Country::Fi] {
+            assert!(!country.calendar().holidays(2024).unwrap().is_empty());
+        }
+    }
+}
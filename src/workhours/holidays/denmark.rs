@@ -0,0 +1,122 @@
+//! Danish holiday calendar
+
+use super::HolidayKind::{CustomaryDayOff, PublicHoliday};
+use super::{easter_relative, fixed_date, Holiday, HolidayCalendar};
+
+/// The last year in which Store bededag was a Danish public holiday
+const LAST_STORE_BEDEDAG: i32 = 2023;
+
+/// The Danish holiday calendar
+///
+/// Contains the public holidays (helligdage) together with Grundlovsdag and
+/// Juleaftensdag, which are days off under most Danish agreements.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DanishCalendar;
+
+impl HolidayCalendar for DanishCalendar {
+    fn holidays(&self, year: i32) -> Result<Vec<Holiday>, String> {
+        let mut holidays = vec![
+            Holiday::new(
+                fixed_date(year, 1, 1)?,
+                "Nytårsdag",
+                "New Year's Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, -3)?,
+                "Skærtorsdag",
+                "Maundy Thursday",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, -2)?,
+                "Langfredag",
+                "Good Friday",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, 0)?,
+                "Påskedag",
+                "Easter Sunday",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, 1)?,
+                "Anden påskedag",
+                "Easter Monday",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, 39)?,
+                "Kristi himmelfartsdag",
+                "Ascension Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, 49)?,
+                "Pinsedag",
+                "Whit Sunday",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, 50)?,
+                "Anden pinsedag",
+                "Whit Monday",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                fixed_date(year, 6, 5)?,
+                "Grundlovsdag",
+                "Constitution Day",
+                CustomaryDayOff,
+            ),
+            Holiday::new(
+                fixed_date(year, 12, 24)?,
+                "Juleaftensdag",
+                "Christmas Eve",
+                CustomaryDayOff,
+            ),
+            Holiday::new(
+                fixed_date(year, 12, 25)?,
+                "Juledag",
+                "Christmas Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                fixed_date(year, 12, 26)?,
+                "Anden juledag",
+                "Boxing Day",
+                PublicHoliday,
+            ),
+        ];
+        if year <= LAST_STORE_BEDEDAG {
+            holidays.push(Holiday::new(
+                easter_relative(year, 26)?,
+                "Store bededag",
+                "General Prayer Day",
+                PublicHoliday,
+            ));
+        }
+        Ok(holidays)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn store_bededag_until_2023() {
+        let store_bededag = |year| {
+            DanishCalendar
+                .holidays(year)
+                .unwrap()
+                .into_iter()
+                .find(|holiday| holiday.name == "Store bededag")
+                .map(|holiday| holiday.date)
+        };
+        assert_eq!(store_bededag(2023), NaiveDate::from_ymd_opt(2023, 5, 5));
+        assert_eq!(store_bededag(2024), None);
+    }
+}
@@ -0,0 +1,148 @@
+//! Finnish holiday calendar
+
+use super::HolidayKind::{CustomaryDayOff, PublicHoliday};
+use super::{easter_relative, first_weekday_from, fixed_date, Holiday, HolidayCalendar};
+use chrono::Weekday;
+
+/// The Finnish holiday calendar
+///
+/// Contains the public holidays (pyhäpäivät) together with Juhannusaatto and
+/// Jouluaatto, which are days off under most Finnish agreements.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FinnishCalendar;
+
+impl HolidayCalendar for FinnishCalendar {
+    fn holidays(&self, year: i32) -> Result<Vec<Holiday>, String> {
+        Ok(vec![
+            Holiday::new(
+                fixed_date(year, 1, 1)?,
+                "Uudenvuodenpäivä",
+                "New Year's Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                fixed_date(year, 1, 6)?,
+                "Loppiainen",
+                "Epiphany",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, -2)?,
+                "Pitkäperjantai",
+                "Good Friday",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, 0)?,
+                "Pääsiäispäivä",
+                "Easter Sunday",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, 1)?,
+                "Toinen pääsiäispäivä",
+                "Easter Monday",
+                PublicHoliday,
+            ),
+            Holiday::new(fixed_date(year, 5, 1)?, "Vappu", "May Day", PublicHoliday),
+            Holiday::new(
+                easter_relative(year, 39)?,
+                "Helatorstai",
+                "Ascension Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, 49)?,
+                "Helluntaipäivä",
+                "Whit Sunday",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                first_weekday_from(year, 6, 19, Weekday::Fri)?,
+                "Juhannusaatto",
+                "Midsummer Eve",
+                CustomaryDayOff,
+            ),
+            Holiday::new(
+                first_weekday_from(year, 6, 20, Weekday::Sat)?,
+                "Juhannuspäivä",
+                "Midsummer Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                first_weekday_from(year, 10, 31, Weekday::Sat)?,
+                "Pyhäinpäivä",
+                "All Saints' Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                fixed_date(year, 12, 6)?,
+                "Itsenäisyyspäivä",
+                "Independence Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                fixed_date(year, 12, 24)?,
+                "Jouluaatto",
+                "Christmas Eve",
+                CustomaryDayOff,
+            ),
+            Holiday::new(
+                fixed_date(year, 12, 25)?,
+                "Joulupäivä",
+                "Christmas Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                fixed_date(year, 12, 26)?,
+                "Tapaninpäivä",
+                "St. Stephen's Day",
+                PublicHoliday,
+            ),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn date_of(year: i32, name: &str) -> NaiveDate {
+        FinnishCalendar
+            .holidays(year)
+            .unwrap()
+            .into_iter()
+            .find(|holiday| holiday.name == name)
+            .unwrap()
+            .date
+    }
+
+    #[test]
+    fn midsummer_2024() {
+        assert_eq!(
+            date_of(2024, "Juhannusaatto"),
+            NaiveDate::from_ymd_opt(2024, 6, 21).unwrap()
+        );
+        assert_eq!(
+            date_of(2024, "Juhannuspäivä"),
+            NaiveDate::from_ymd_opt(2024, 6, 22).unwrap()
+        );
+    }
+
+    #[test]
+    fn all_saints_day() {
+        assert_eq!(
+            date_of(2024, "Pyhäinpäivä"),
+            NaiveDate::from_ymd_opt(2024, 11, 2).unwrap()
+        );
+        assert_eq!(
+            date_of(2025, "Pyhäinpäivä"),
+            NaiveDate::from_ymd_opt(2025, 11, 1).unwrap()
+        );
+        assert_eq!(
+            date_of(2026, "Pyhäinpäivä"),
+            NaiveDate::from_ymd_opt(2026, 10, 31).unwrap()
+        );
+    }
+}
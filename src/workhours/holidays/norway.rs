@@ -0,0 +1,122 @@
+//! Norwegian holiday calendar
+
+use super::HolidayKind::PublicHoliday;
+use super::{easter_relative, fixed_date, Holiday, HolidayCalendar};
+
+/// The Norwegian holiday calendar
+///
+/// Contains the public holidays (helligdager) and the two official flag days
+/// that are also public holidays, Arbeidernes dag and Grunnlovsdag.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NorwegianCalendar;
+
+impl HolidayCalendar for NorwegianCalendar {
+    fn holidays(&self, year: i32) -> Result<Vec<Holiday>, String> {
+        Ok(vec![
+            Holiday::new(
+                fixed_date(year, 1, 1)?,
+                "Første nyttårsdag",
+                "New Year's Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, -3)?,
+                "Skjærtorsdag",
+                "Maundy Thursday",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, -2)?,
+                "Langfredag",
+                "Good Friday",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, 0)?,
+                "Første påskedag",
+                "Easter Sunday",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, 1)?,
+                "Andre påskedag",
+                "Easter Monday",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                fixed_date(year, 5, 1)?,
+                "Arbeidernes dag",
+                "Labour Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                fixed_date(year, 5, 17)?,
+                "Grunnlovsdag",
+                "Constitution Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, 39)?,
+                "Kristi himmelfartsdag",
+                "Ascension Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, 49)?,
+                "Første pinsedag",
+                "Whit Sunday",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, 50)?,
+                "Andre pinsedag",
+                "Whit Monday",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                fixed_date(year, 12, 25)?,
+                "Første juledag",
+                "Christmas Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                fixed_date(year, 12, 26)?,
+                "Andre juledag",
+                "Boxing Day",
+                PublicHoliday,
+            ),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn holidays_2024() {
+        let dates: Vec<NaiveDate> = NorwegianCalendar
+            .holidays(2024)
+            .unwrap()
+            .iter()
+            .map(|holiday| holiday.date)
+            .collect();
+        let expected = [
+            (1, 1),
+            (3, 28),
+            (3, 29),
+            (3, 31),
+            (4, 1),
+            (5, 1),
+            (5, 17),
+            (5, 9),
+            (5, 19),
+            (5, 20),
+            (12, 25),
+            (12, 26),
+        ]
+        .map(|(month, day)| NaiveDate::from_ymd_opt(2024, month, day).unwrap());
+        assert_eq!(dates, expected);
+    }
+}
@@ -0,0 +1,395 @@
+//! Swedish holiday calendar
+//!
+//! Public holidays follow Lagen om allmänna helgdagar (1989:253). Midsommarafton,
+//! julafton and nyårsafton are not public holidays, but are treated as such by
+//! Arbetstidslagen and are days off in practice.
+//!
+//! Many collective agreements also shorten the working day on a number of eves. These
+//! are not part of the calendar by default, but can be added with
+//! `SwedishCalendar::with_reduced_eve` or `SwedishCalendar::with_reduced_eves`.
+
+use super::HolidayKind::{CustomaryDayOff, PublicHoliday, ReducedHours};
+use super::{easter_relative, first_weekday_from, fixed_date, Holiday, HolidayCalendar};
+use chrono::{NaiveDate, Weekday};
+
+/// The first year in which Sveriges nationaldag replaced Annandag pingst as a public holiday
+const FIRST_NATIONAL_DAY_HOLIDAY: i32 = 2005;
+
+/// The eves commonly shortened by Swedish collective agreements
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwedishEve {
+    /// Trettondagsafton, 5 January
+    Trettondagsafton,
+    /// Skärtorsdagen, the Thursday before Easter
+    Skartorsdagen,
+    /// Valborgsmässoafton, 30 April
+    Valborgsmassoafton,
+    /// The day before Kristi himmelsfärdsdag
+    KristiHimmelsfardsafton,
+    /// Allhelgonaafton, the Friday before Alla helgons dag
+    Allhelgonaafton,
+}
+
+impl SwedishEve {
+    /// All eves, in the order they occur in a year
+    pub const ALL: [SwedishEve; 5] = [
+        SwedishEve::Trettondagsafton,
+        SwedishEve::Skartorsdagen,
+        SwedishEve::Valborgsmassoafton,
+        SwedishEve::KristiHimmelsfardsafton,
+        SwedishEve::Allhelgonaafton,
+    ];
+
+    /// Calculates the date of the eve in the given year
+    fn date(&self, year: i32) -> Result<NaiveDate, String> {
+        match self {
+            SwedishEve::Trettondagsafton => fixed_date(year, 1, 5),
+            SwedishEve::Skartorsdagen => easter_relative(year, -3),
+            SwedishEve::Valborgsmassoafton => fixed_date(year, 4, 30),
+            SwedishEve::KristiHimmelsfardsafton => easter_relative(year, 38),
+            SwedishEve::Allhelgonaafton => first_weekday_from(year, 10, 30, Weekday::Fri),
+        }
+    }
+
+    /// Returns the Swedish and English names of the eve
+    fn names(&self) -> (&'static str, &'static str) {
+        match self {
+            SwedishEve::Trettondagsafton => ("Trettondagsafton", "Twelfth Night"),
+            SwedishEve::Skartorsdagen => ("Skärtorsdagen", "Maundy Thursday"),
+            SwedishEve::Valborgsmassoafton => ("Valborgsmässoafton", "Walpurgis Night"),
+            SwedishEve::KristiHimmelsfardsafton => (
+                "Dagen före Kristi himmelsfärdsdag",
+                "Day before Ascension Day",
+            ),
+            SwedishEve::Allhelgonaafton => ("Allhelgonaafton", "All Saints' Eve"),
+        }
+    }
+}
+
+/// The Swedish holiday calendar
+///
+/// This is the default calendar used by the work hours calculator.
+///
+/// # Example
+///
+/// ```
+/// use time_tally::workhours::holidays::{HolidayCalendar, HolidayKind, SwedishCalendar, SwedishEve};
+///
+/// let calendar = SwedishCalendar::default().with_reduced_eve(SwedishEve::Valborgsmassoafton, 4);
+/// let holidays = calendar.holidays(2024).unwrap();
+/// assert!(holidays.iter().any(|holiday| holiday.kind == HolidayKind::ReducedHours(4)));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SwedishCalendar {
+    /// Eves shortened by a collective agreement, with the work hours on each
+    reduced_eves: Vec<(SwedishEve, u32)>,
+}
+
+impl SwedishCalendar {
+    /// Shortens the working day on an eve
+    ///
+    /// # Arguments
+    ///
+    /// * `eve` - The eve to shorten
+    /// * `hours` - The work hours on the eve
+    pub fn with_reduced_eve(mut self, eve: SwedishEve, hours: u32) -> Self {
+        self.reduced_eves.retain(|(reduced, _)| *reduced != eve);
+        self.reduced_eves.push((eve, hours));
+        self
+    }
+
+    /// Shortens the working day on all eves in `SwedishEve::ALL`
+    ///
+    /// # Arguments
+    ///
+    /// * `hours` - The work hours on each eve
+    pub fn with_reduced_eves(self, hours: u32) -> Self {
+        SwedishEve::ALL
+            .iter()
+            .fold(self, |calendar, eve| calendar.with_reduced_eve(*eve, hours))
+    }
+}
+
+impl HolidayCalendar for SwedishCalendar {
+    fn holidays(&self, year: i32) -> Result<Vec<Holiday>, String> {
+        let mut holidays = vec![
+            Holiday::new(
+                fixed_date(year, 1, 1)?,
+                "Nyårsdagen",
+                "New Year's Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                fixed_date(year, 1, 6)?,
+                "Trettondedag jul",
+                "Epiphany",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, -2)?,
+                "Långfredagen",
+                "Good Friday",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, 0)?,
+                "Påskdagen",
+                "Easter Sunday",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, 1)?,
+                "Annandag påsk",
+                "Easter Monday",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                fixed_date(year, 5, 1)?,
+                "Första maj",
+                "May Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, 39)?,
+                "Kristi himmelsfärdsdag",
+                "Ascension Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                easter_relative(year, 49)?,
+                "Pingstdagen",
+                "Whit Sunday",
+                PublicHoliday,
+            ),
+        ];
+        if year < FIRST_NATIONAL_DAY_HOLIDAY {
+            holidays.push(Holiday::new(
+                easter_relative(year, 50)?,
+                "Annandag pingst",
+                "Whit Monday",
+                PublicHoliday,
+            ));
+        } else {
+            holidays.push(Holiday::new(
+                fixed_date(year, 6, 6)?,
+                "Sveriges nationaldag",
+                "National Day of Sweden",
+                PublicHoliday,
+            ));
+        }
+        holidays.extend([
+            Holiday::new(
+                first_weekday_from(year, 6, 19, Weekday::Fri)?,
+                "Midsommarafton",
+                "Midsummer Eve",
+                CustomaryDayOff,
+            ),
+            Holiday::new(
+                first_weekday_from(year, 6, 20, Weekday::Sat)?,
+                "Midsommardagen",
+                "Midsummer Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                first_weekday_from(year, 10, 31, Weekday::Sat)?,
+                "Alla helgons dag",
+                "All Saints' Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                fixed_date(year, 12, 24)?,
+                "Julafton",
+                "Christmas Eve",
+                CustomaryDayOff,
+            ),
+            Holiday::new(
+                fixed_date(year, 12, 25)?,
+                "Juldagen",
+                "Christmas Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                fixed_date(year, 12, 26)?,
+                "Annandag jul",
+                "Boxing Day",
+                PublicHoliday,
+            ),
+            Holiday::new(
+                fixed_date(year, 12, 31)?,
+                "Nyårsafton",
+                "New Year's Eve",
+                CustomaryDayOff,
+            ),
+        ]);
+        for (eve, hours) in &self.reduced_eves {
+            let (name, english_name) = eve.names();
+            holidays.push(Holiday::new(
+                eve.date(year)?,
+                name,
+                english_name,
+                ReducedHours(*hours),
+            ));
+        }
+        Ok(holidays)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workhours::holidays::HolidayKind;
+    use chrono::{Datelike, NaiveDate};
+
+    fn date_of(year: i32, name: &str) -> NaiveDate {
+        SwedishCalendar::default()
+            .holidays(year)
+            .unwrap()
+            .into_iter()
+            .find(|holiday| holiday.name == name)
+            .unwrap_or_else(|| panic!("{} missing in {}", name, year))
+            .date
+    }
+
+    fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn holidays_2024() {
+        let holidays: Vec<(NaiveDate, String, HolidayKind)> = SwedishCalendar::default()
+            .holidays(2024)
+            .unwrap()
+            .into_iter()
+            .map(|holiday| (holiday.date, holiday.name, holiday.kind))
+            .collect();
+        let expected = [
+            (ymd(2024, 1, 1), "Nyårsdagen", PublicHoliday),
+            (ymd(2024, 1, 6), "Trettondedag jul", PublicHoliday),
+            (ymd(2024, 3, 29), "Långfredagen", PublicHoliday),
+            (ymd(2024, 3, 31), "Påskdagen", PublicHoliday),
+            (ymd(2024, 4, 1), "Annandag påsk", PublicHoliday),
+            (ymd(2024, 5, 1), "Första maj", PublicHoliday),
+            (ymd(2024, 5, 9), "Kristi himmelsfärdsdag", PublicHoliday),
+            (ymd(2024, 5, 19), "Pingstdagen", PublicHoliday),
+            (ymd(2024, 6, 6), "Sveriges nationaldag", PublicHoliday),
+            (ymd(2024, 6, 21), "Midsommarafton", CustomaryDayOff),
+            (ymd(2024, 6, 22), "Midsommardagen", PublicHoliday),
+            (ymd(2024, 11, 2), "Alla helgons dag", PublicHoliday),
+            (ymd(2024, 12, 24), "Julafton", CustomaryDayOff),
+            (ymd(2024, 12, 25), "Juldagen", PublicHoliday),
+            (ymd(2024, 12, 26), "Annandag jul", PublicHoliday),
+            (ymd(2024, 12, 31), "Nyårsafton", CustomaryDayOff),
+        ]
+        .map(|(date, name, kind)| (date, name.to_string(), kind));
+        assert_eq!(holidays, expected);
+    }
+
+    //Dates taken from the official Swedish almanac
+    #[test]
+    fn pinned_movable_holidays() {
+        let pinned = [
+            // (year, påskdagen, midsommarafton, alla helgons dag)
+            (2000, (4, 23), (6, 23), (11, 4)),
+            (2004, (4, 11), (6, 25), (11, 6)),
+            (2010, (4, 4), (6, 25), (11, 6)),
+            (2019, (4, 21), (6, 21), (11, 2)),
+            (2024, (3, 31), (6, 21), (11, 2)),
+            (2025, (4, 20), (6, 20), (11, 1)),
+            (2038, (4, 25), (6, 25), (11, 6)),
+            (2100, (3, 28), (6, 25), (11, 6)),
+        ];
+        for (year, easter, midsummer_eve, all_saints) in pinned {
+            assert_eq!(date_of(year, "Påskdagen"), ymd(year, easter.0, easter.1));
+            assert_eq!(
+                date_of(year, "Midsommarafton"),
+                ymd(year, midsummer_eve.0, midsummer_eve.1)
+            );
+            assert_eq!(
+                date_of(year, "Alla helgons dag"),
+                ymd(year, all_saints.0, all_saints.1)
+            );
+        }
+    }
+
+    #[test]
+    fn movable_holidays_2000_to_2100() {
+        for year in 2000..=2100 {
+            let easter = date_of(year, "Påskdagen");
+            assert_eq!(easter.weekday(), Weekday::Sun);
+            assert_eq!(date_of(year, "Långfredagen").weekday(), Weekday::Fri);
+            assert_eq!(date_of(year, "Annandag påsk").weekday(), Weekday::Mon);
+            assert_eq!(
+                date_of(year, "Kristi himmelsfärdsdag").weekday(),
+                Weekday::Thu
+            );
+            assert_eq!(date_of(year, "Pingstdagen").weekday(), Weekday::Sun);
+
+            let midsummer_eve = date_of(year, "Midsommarafton");
+            assert_eq!(midsummer_eve.weekday(), Weekday::Fri);
+            assert_eq!(midsummer_eve.month(), 6);
+            assert!((19..=25).contains(&midsummer_eve.day()));
+            assert_eq!(
+                date_of(year, "Midsommardagen"),
+                midsummer_eve.succ_opt().unwrap()
+            );
+
+            let all_saints = date_of(year, "Alla helgons dag");
+            assert_eq!(all_saints.weekday(), Weekday::Sat);
+            assert!(all_saints >= ymd(year, 10, 31) && all_saints <= ymd(year, 11, 6));
+        }
+    }
+
+    #[test]
+    fn national_day_is_never_moved() {
+        for year in 2005..=2100 {
+            assert_eq!(date_of(year, "Sveriges nationaldag"), ymd(year, 6, 6));
+        }
+    }
+
+    #[test]
+    fn whit_monday_until_2004() {
+        assert_eq!(date_of(2004, "Annandag pingst"), ymd(2004, 5, 31));
+        let holidays_2005 = SwedishCalendar::default().holidays(2005).unwrap();
+        assert!(holidays_2005
+            .iter()
+            .all(|holiday| holiday.name != "Annandag pingst"));
+    }
+
+    #[test]
+    fn no_reduced_eves_by_default() {
+        let holidays = SwedishCalendar::default().holidays(2024).unwrap();
+        assert!(holidays
+            .iter()
+            .all(|holiday| !matches!(holiday.kind, ReducedHours(_))));
+    }
+
+    #[test]
+    fn reduced_eves_2024() {
+        let calendar = SwedishCalendar::default()
+            .with_reduced_eves(4)
+            .with_reduced_eve(SwedishEve::Skartorsdagen, 6);
+        let eves: Vec<(NaiveDate, HolidayKind)> = calendar
+            .holidays(2024)
+            .unwrap()
+            .into_iter()
+            .filter(|holiday| matches!(holiday.kind, ReducedHours(_)))
+            .map(|holiday| (holiday.date, holiday.kind))
+            .collect();
+        assert_eq!(
+            eves,
+            [
+                (ymd(2024, 1, 5), ReducedHours(4)),
+                (ymd(2024, 4, 30), ReducedHours(4)),
+                (ymd(2024, 5, 8), ReducedHours(4)),
+                (ymd(2024, 11, 1), ReducedHours(4)),
+                (ymd(2024, 3, 28), ReducedHours(6)),
+            ]
+        );
+    }
+
+    #[test]
+    fn for_years_covers_every_year() {
+        let holidays = SwedishCalendar::default().for_years(2023, 2025).unwrap();
+        assert_eq!(holidays.len(), 48);
+    }
+}
@@ -0,0 +1,183 @@
+//! # Hours
+//!
+//! An amount of work time, kept as whole minutes so that sums over many days stay exact.
+
+use super::MAX_RANGE_DAYS;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+/// An amount of work time with minute precision
+///
+/// Serializes as a number of hours: whole hours as an integer (e.g. `40`) and
+/// anything else as a decimal number (e.g. `37.5`). Deserializes from a number of hours,
+/// rounded to the nearest minute, of at most `Hours::MAX` either way.
+///
+/// # Example
+///
+/// ```
+/// use time_tally::workhours::Hours;
+///
+/// let week = Hours::from_hours(32) + Hours::from_minutes(330);
+/// assert_eq!(week.minutes(), 2250);
+/// assert_eq!(week.as_f64(), 37.5);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hours(i64);
+
+impl Hours {
+    /// No work time
+    pub const ZERO: Hours = Hours(0);
+
+    /// The most work time accepted as input: every hour of the longest range
+    pub const MAX: Hours = Hours::from_hours(MAX_RANGE_DAYS * 24);
+
+    /// Creates an amount of work time from minutes
+    pub const fn from_minutes(minutes: i64) -> Self {
+        Hours(minutes)
+    }
+
+    /// Creates an amount of work time from whole hours
+    pub const fn from_hours(hours: i64) -> Self {
+        Hours(hours * 60)
+    }
+
+    /// Returns the work time in minutes
+    pub const fn minutes(&self) -> i64 {
+        self.0
+    }
+
+    /// Adds two amounts of work time, returning `None` on overflow
+    pub const fn checked_add(self, rhs: Hours) -> Option<Hours> {
+        match self.0.checked_add(rhs.0) {
+            Some(minutes) => Some(Hours(minutes)),
+            None => None,
+        }
+    }
+
+    /// Returns the work time in hours
+    pub fn as_f64(&self) -> f64 {
+        self.0 as f64 / 60.0
+    }
+}
+
+impl fmt::Display for Hours {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        write!(f, "{}{}:{:02}", sign, self.0.abs() / 60, self.0.abs() % 60)
+    }
+}
+
+impl Serialize for Hours {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.0 % 60 == 0 {
+            serializer.serialize_i64(self.0 / 60)
+        } else {
+            serializer.serialize_f64(self.as_f64())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Hours {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hours = f64::deserialize(deserializer)?;
+        let minutes = (hours * 60.0).round();
+        if !minutes.is_finite() || minutes.abs() > Hours::MAX.0 as f64 {
+            return Err(de::Error::custom(format!(
+                "invalid number of hours {}",
+                hours
+            )));
+        }
+        Ok(Hours(minutes as i64))
+    }
+}
+
+impl Add for Hours {
+    type Output = Hours;
+
+    fn add(self, rhs: Hours) -> Hours {
+        Hours(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Hours {
+    fn add_assign(&mut self, rhs: Hours) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for Hours {
+    type Output = Hours;
+
+    fn sub(self, rhs: Hours) -> Hours {
+        Hours(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for Hours {
+    fn sub_assign(&mut self, rhs: Hours) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Sum for Hours {
+    fn sum<I: Iterator<Item = Hours>>(iter: I) -> Hours {
+        iter.fold(Hours::ZERO, Add::add)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_whole_hours_as_integers() {
+        assert_eq!(serde_json::to_string(&Hours::from_hours(40)).unwrap(), "40");
+        assert_eq!(
+            serde_json::to_string(&Hours::from_minutes(450)).unwrap(),
+            "7.5"
+        );
+    }
+
+    #[test]
+    fn deserializes_hours_to_the_nearest_minute() {
+        assert_eq!(
+            serde_json::from_str::<Hours>("7.5").unwrap(),
+            Hours::from_minutes(450)
+        );
+        assert_eq!(
+            serde_json::from_str::<Hours>("8").unwrap(),
+            Hours::from_hours(8)
+        );
+        assert_eq!(
+            serde_json::from_str::<Hours>("0.333").unwrap(),
+            Hours::from_minutes(20)
+        );
+        assert!(serde_json::from_str::<Hours>("\"8\"").is_err());
+    }
+
+    #[test]
+    fn rejects_implausible_hours() {
+        assert_eq!(
+            serde_json::from_str::<Hours>("-878400").unwrap(),
+            Hours::from_hours(-878400)
+        );
+        assert!(serde_json::from_str::<Hours>("878401").is_err());
+        assert!(serde_json::from_str::<Hours>("1e17").is_err());
+        assert_eq!(
+            Hours::MAX.checked_add(Hours::MAX),
+            Some(Hours::from_hours(2 * 878400))
+        );
+        assert_eq!(
+            Hours::from_minutes(i64::MAX).checked_add(Hours::from_minutes(1)),
+            None
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Hours::from_minutes(450).to_string(), "7:30");
+        assert_eq!(Hours::from_minutes(-90).to_string(), "-1:30");
+    }
+}
@@ -0,0 +1,386 @@
+//! # Overtime
+//!
+//! This module splits the time worked beyond the work hours of each day into the two kinds
+//! of overtime of most Swedish collective agreements:
+//!
+//! - Simple overtime ("enkel övertid"): on weekdays between 06:00 and 20:00
+//! - Qualified overtime ("kvalificerad övertid"): on weekdays between 20:00 and 06:00, and at
+//!   any time on weekends, public holidays and customary days off such as midsommarafton
+//!
+//! The time worked on a day first makes up its work hours in the order it was worked, so
+//! the latest time worked is overtime.
+//!
+//! ## Format
+//!
+//! Time worked is written as semicolon-separated `DATE:HH:MM-HH:MM` entries, where the date
+//! is in any of the formats accepted for the start and end of a range except ISO weeks, e.g.
+//! `2024-06-03:08:00-17:00;2024-06-03:19:00-22:30`. An interval ending at or before its start
+//! continues past midnight into the next day, e.g. `2024-06-07:22:00-02:00`.
+
+use super::date_input::{parse_single_day, DateInputError};
+use super::holidays::{Holiday, HolidayKind};
+use super::{parse_dates, periods_between, CalculationOptions, Hours, PeriodId, WorkhoursError};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// The minutes in a day
+const DAY: u32 = 24 * 60;
+
+/// The time of day simple overtime starts on weekdays, in minutes after midnight
+const SIMPLE_FROM: u32 = 6 * 60;
+
+/// The time of day simple overtime ends on weekdays, in minutes after midnight
+const SIMPLE_UNTIL: u32 = 20 * 60;
+
+/// Time intervals worked by date
+///
+/// Each interval is kept as minutes after the midnight starting its date, so intervals
+/// continuing past midnight end after `24:00`.
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use time_tally::workhours::{Hours, WorkedTime};
+///
+/// let worked: WorkedTime = "2024-06-07:08:00-12:00;2024-06-07:22:00-02:00".parse().unwrap();
+/// let june_7 = NaiveDate::from_ymd_opt(2024, 6, 7).unwrap();
+/// assert_eq!(worked.on(june_7), Hours::from_hours(8));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkedTime(BTreeMap<NaiveDate, Vec<(u32, u32)>>);
+
+impl WorkedTime {
+    /// Returns the time worked in the intervals starting on a date
+    pub fn on(&self, date: NaiveDate) -> Hours {
+        self.intervals(date)
+            .iter()
+            .map(|(from, to)| Hours::from_minutes((to - from) as i64))
+            .sum()
+    }
+
+    /// Returns the intervals starting on a date in order
+    fn intervals(&self, date: NaiveDate) -> &[(u32, u32)] {
+        self.0.get(&date).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Parses a time of day "HH:MM" between 00:00 and 24:00 into minutes after midnight
+fn parse_time(time: &str) -> Option<u32> {
+    let (hours, minutes) = time.trim().split_once(':')?;
+    if hours.len() != 2 || minutes.len() != 2 {
+        return None;
+    }
+    let minutes =
+        hours.parse::<u32>().ok()? * 60 + minutes.parse::<u32>().ok().filter(|m| *m < 60)?;
+    (minutes <= DAY).then_some(minutes)
+}
+
+impl FromStr for WorkedTime {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut worked: BTreeMap<NaiveDate, Vec<(u32, u32)>> = BTreeMap::new();
+        for entry in s.split(';').filter(|entry| !entry.trim().is_empty()) {
+            let invalid = || {
+                format!(
+                    "Invalid worked time '{}': expected DATE:HH:MM-HH:MM, e.g. 2024-06-03:08:00-17:00",
+                    entry
+                )
+            };
+            let (date, interval) = entry.split_once(':').ok_or_else(invalid)?;
+            let date = parse_single_day(date).map_err(|err| match err {
+                DateInputError::Invalid => invalid(),
+                DateInputError::Ambiguous => format!(
+                    "Invalid worked time '{}': ambiguous date '{}'",
+                    entry,
+                    date.trim()
+                ),
+            })?;
+            let (from, to) = interval.split_once('-').ok_or_else(invalid)?;
+            let from = parse_time(from)
+                .filter(|from| *from < DAY)
+                .ok_or_else(invalid)?;
+            let to = parse_time(to).ok_or_else(invalid)?;
+            let to = if to <= from { to + DAY } else { to };
+            worked.entry(date).or_default().push((from, to));
+        }
+
+        //Intervals may continue into the next day, so overlaps are checked across dates
+        let mut all: Vec<(NaiveDate, u32, u32)> = Vec::new();
+        for (date, intervals) in worked.iter_mut() {
+            intervals.sort_unstable();
+            all.extend(intervals.iter().map(|(from, to)| (*date, *from, *to)));
+        }
+        for pair in all.windows(2) {
+            let [(date, _, to), (next_date, next_from, _)] = pair else {
+                continue;
+            };
+            let gap = (*next_date - *date).num_days() * DAY as i64 + *next_from as i64;
+            if gap < *to as i64 {
+                return Err(format!(
+                    "Invalid worked time: overlapping intervals on {}",
+                    next_date
+                ));
+            }
+        }
+        Ok(WorkedTime(worked))
+    }
+}
+
+/// The overtime of a single reporting period
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct OvertimePeriod {
+    /// The unique identifier of the period, e.g. "2024-06-W23"
+    pub id: PeriodId,
+    /// The name of the period, e.g. "week: 23"
+    pub name: String,
+    /// The first date of the period within the range
+    pub start: NaiveDate,
+    /// The last date of the period within the range
+    pub end: NaiveDate,
+    /// The time worked in intervals starting in the period
+    pub worked: Hours,
+    /// The work hours of the period, net of any absence
+    pub expected: Hours,
+    /// Simple overtime ("enkel övertid")
+    pub simple: Hours,
+    /// Qualified overtime ("kvalificerad övertid")
+    pub qualified: Hours,
+}
+
+/// The overtime of a range by reporting period
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct OvertimeReport {
+    /// The first date of the range
+    pub start: NaiveDate,
+    /// The last date of the range
+    pub end: NaiveDate,
+    /// The time worked across the range
+    pub worked: Hours,
+    /// The work hours across the range, net of any absence
+    pub expected: Hours,
+    /// Simple overtime across the range
+    pub simple: Hours,
+    /// Qualified overtime across the range
+    pub qualified: Hours,
+    /// The reporting periods of the range in date order
+    pub periods: Vec<OvertimePeriod>,
+}
+
+/// Whether all overtime on a date is qualified: weekends, public holidays and days off
+fn is_qualified_day(date: NaiveDate, holidays: &[Holiday]) -> bool {
+    matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+        || holidays.iter().any(|holiday| {
+            holiday.date == date
+                && matches!(
+                    holiday.kind,
+                    HolidayKind::PublicHoliday | HolidayKind::CustomaryDayOff
+                )
+        })
+}
+
+/// Splits overtime between two times into simple and qualified overtime
+///
+/// # Arguments
+///
+/// * `date` - The date the times are counted from
+/// * `from` - The start of the overtime in minutes after the midnight starting `date`
+/// * `to` - The end of the overtime in minutes after the same midnight
+/// * `holidays` - The holidays of the calendar around the date
+///
+/// # Returns
+///
+/// The simple and qualified overtime in minutes
+fn classify(date: NaiveDate, from: u32, to: u32, holidays: &[Holiday]) -> (u32, u32) {
+    let (mut simple, mut qualified) = (0, 0);
+    let mut time = from;
+    while time < to {
+        let (day, minute) = (time / DAY, time % DAY);
+        let boundary = [SIMPLE_FROM, SIMPLE_UNTIL, DAY]
+            .into_iter()
+            .find(|boundary| *boundary > minute)
+            .unwrap_or(DAY);
+        let until = to.min(day * DAY + boundary);
+        let day_date = date + Duration::days(day as i64);
+        if !is_qualified_day(day_date, holidays) && (SIMPLE_FROM..SIMPLE_UNTIL).contains(&minute) {
+            simple += until - time;
+        } else {
+            qualified += until - time;
+        }
+        time = until;
+    }
+    (simple, qualified)
+}
+
+/// Calculates the simple and qualified overtime of a range by reporting period
+///
+/// # Arguments
+///
+/// * `start` - Start date in any of the formats accepted by `calculate_workhours`
+/// * `end` - End date in any of the formats accepted by `calculate_workhours`
+/// * `worked` - The time intervals worked
+/// * `options` - The holiday calendar, work schedule, employment rate and absences deciding
+///   the work hours of each day
+///
+/// # Returns
+///
+/// A `Result` containing the overtime of each period and the whole range, or a
+/// `WorkhoursError` if not
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The date strings are not in any of the accepted formats, or are ambiguous
+/// - The start date is after the end date or the range spans more than `MAX_RANGE_DAYS` days
+/// - Time is worked in intervals starting outside the range
+/// - There's an issue calculating holidays or period boundaries
+///
+/// # Example
+///
+/// ```
+/// use time_tally::workhours::{calculate_overtime, CalculationOptions, Hours};
+///
+/// //8 hours of work and 3 hours of overtime, of which 1 hour after 20:00
+/// let worked = "2024-06-03:08:00-16:00;2024-06-03:18:00-21:00".parse().unwrap();
+/// let overtime = calculate_overtime("03-06-2024".to_string(), "03-06-2024".to_string(), &worked, &CalculationOptions::default()).unwrap();
+/// assert_eq!(overtime.simple, Hours::from_hours(2));
+/// assert_eq!(overtime.qualified, Hours::from_hours(1));
+/// ```
+pub fn calculate_overtime(
+    start: String,
+    end: String,
+    worked: &WorkedTime,
+    options: &CalculationOptions,
+) -> Result<OvertimeReport, WorkhoursError> {
+    let (start_date, end_date) = parse_dates(start, end)?;
+    if let Some(date) = worked
+        .0
+        .keys()
+        .find(|date| **date < start_date || end_date < **date)
+    {
+        return Err(WorkhoursError::WorkedOutsideRange(date.to_string()));
+    }
+    //Intervals on the last day may continue into the next year
+    let holidays = options
+        .calendar
+        .for_years(start_date.year(), end_date.year() + 1)
+        .map_err(WorkhoursError::Calendar)?;
+
+    let mut periods = Vec::new();
+    for period in periods_between(&start_date, &end_date, options)? {
+        let (mut simple, mut qualified) = (0, 0);
+        for day in &period.days {
+            //The earliest time worked makes up the work hours, the rest is overtime
+            let mut regular = day.hours.minutes().max(0) as u32;
+            for (from, to) in worked.intervals(day.date) {
+                let overtime_from = (*from + regular).min(*to);
+                regular -= overtime_from - from;
+                let (day_simple, day_qualified) = classify(day.date, overtime_from, *to, &holidays);
+                simple += day_simple;
+                qualified += day_qualified;
+            }
+        }
+        periods.push(OvertimePeriod {
+            id: period.id,
+            name: period.name,
+            start: period.start,
+            end: period.end,
+            worked: period.days.iter().map(|day| worked.on(day.date)).sum(),
+            expected: period.hours,
+            simple: Hours::from_minutes(simple as i64),
+            qualified: Hours::from_minutes(qualified as i64),
+        });
+    }
+
+    Ok(OvertimeReport {
+        start: start_date,
+        end: end_date,
+        worked: periods.iter().map(|period| period.worked).sum(),
+        expected: periods.iter().map(|period| period.expected).sum(),
+        simple: periods.iter().map(|period| period.simple).sum(),
+        qualified: periods.iter().map(|period| period.qualified).sum(),
+        periods,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overtime(start: &str, end: &str, worked: &str) -> OvertimeReport {
+        calculate_overtime(
+            start.to_string(),
+            end.to_string(),
+            &worked.parse().unwrap(),
+            &CalculationOptions::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_worked_time() {
+        let worked: WorkedTime = "2024-06-03:17:00-08:00;2024-06-03:07:00-16:00"
+            .parse()
+            .unwrap();
+        let june_3 = NaiveDate::from_ymd_opt(2024, 6, 3).unwrap();
+        assert_eq!(worked.intervals(june_3), &[(420, 960), (1020, 1920)]);
+
+        for invalid in [
+            "2024-06-03:08:00",
+            "2024-06-03:8:00-17:00",
+            "2024-06-03:08:00-24:01",
+            "2024-W23:08:00-17:00",
+            "2024-06-03:08:00-12:00;2024-06-03:11:00-13:00",
+            "2024-06-03:22:00-02:00;2024-06-04:01:00-08:00",
+        ] {
+            assert!(invalid.parse::<WorkedTime>().is_err(), "{}", invalid);
+        }
+    }
+
+    //Mon 3 jun 2024: 8 hours of work from 07:00, then overtime until 22:00
+    #[test]
+    fn evenings_are_qualified() {
+        let report = overtime("2024-06-03", "2024-06-03", "2024-06-03:07:00-22:00");
+        assert_eq!(report.worked, Hours::from_hours(15));
+        assert_eq!(report.simple, Hours::from_hours(5));
+        assert_eq!(report.qualified, Hours::from_hours(2));
+    }
+
+    //Nationaldagen on thu 6 jun and the weekend of 8-9 jun 2024 have no work hours
+    #[test]
+    fn holidays_and_weekends_are_qualified() {
+        let report = overtime(
+            "2024-06-05",
+            "2024-06-09",
+            "2024-06-06:10:00-14:00;2024-06-08:09:00-11:00;2024-06-05:04:00-14:00",
+        );
+        assert_eq!(report.expected, Hours::from_hours(16));
+        assert_eq!(report.simple, Hours::from_hours(2));
+        assert_eq!(report.qualified, Hours::from_hours(4 + 2));
+    }
+
+    //Fri 7 jun 2024 into the weekend: after the work hours, 20:00 to 02:00 is qualified
+    #[test]
+    fn overtime_past_midnight() {
+        let report = overtime("2024-06-07", "2024-06-07", "2024-06-07:12:00-02:00");
+        assert_eq!(report.simple, Hours::ZERO);
+        assert_eq!(report.qualified, Hours::from_hours(6));
+        assert_eq!(report.periods[0].worked, Hours::from_hours(14));
+    }
+
+    #[test]
+    fn splits_by_period() {
+        let report = overtime(
+            "2024-05-31",
+            "2024-06-03",
+            "2024-05-31:08:00-18:00;2024-06-03:08:00-17:00",
+        );
+        assert_eq!(report.periods.len(), 2);
+        assert_eq!(report.periods[0].simple, Hours::from_hours(2));
+        assert_eq!(report.periods[1].simple, Hours::from_hours(1));
+        assert_eq!(report.simple, Hours::from_hours(3));
+    }
+}
@@ -0,0 +1,253 @@
+//! # Period Calendars
+//!
+//! This module defines `PeriodCalendar`, the list of all reporting periods in a year,
+//! and renders it as JSON, CSV or an iCalendar feed with the closing day of each period.
+
+use super::{Hours, Period, PeriodId};
+use chrono::{Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// The formats a period calendar can be rendered in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PeriodFormat {
+    /// A JSON document
+    #[default]
+    Json,
+    /// Comma-separated values with a header row
+    Csv,
+    /// An iCalendar feed with an all-day event on the last day of each period
+    Ics,
+}
+
+impl PeriodFormat {
+    /// Returns the media type of the format, e.g. "text/csv"
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            PeriodFormat::Json => "application/json",
+            PeriodFormat::Csv => "text/csv; charset=utf-8",
+            PeriodFormat::Ics => "text/calendar; charset=utf-8",
+        }
+    }
+}
+
+impl FromStr for PeriodFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(PeriodFormat::Json),
+            "csv" => Ok(PeriodFormat::Csv),
+            "ics" => Ok(PeriodFormat::Ics),
+            _ => Err(format!("Invalid format '{}': expected json, csv or ics", s)),
+        }
+    }
+}
+
+/// All reporting periods in a year
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct PeriodCalendar {
+    /// The year of the periods
+    pub year: i32,
+    /// The periods of the year in date order
+    pub periods: Vec<CalendarPeriod>,
+}
+
+/// A single reporting period in a `PeriodCalendar`
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct CalendarPeriod {
+    /// The number of the period within the year, starting at 1
+    pub number: u32,
+    /// The unique identifier of the period, e.g. "2024-06-W23"
+    pub id: PeriodId,
+    /// The name of the period, e.g. "week: 23"
+    pub name: String,
+    /// The first date of the period
+    pub start: NaiveDate,
+    /// The last date of the period, when it closes for reporting
+    pub end: NaiveDate,
+    /// The number of days with work hours
+    pub working_days: u32,
+    /// The work hours of the period
+    pub hours: Hours,
+}
+
+impl PeriodCalendar {
+    /// Numbers the periods of a year
+    ///
+    /// # Arguments
+    ///
+    /// * `year` - The year of the periods
+    /// * `periods` - The periods of the year in date order
+    pub(super) fn from_periods(year: i32, periods: Vec<Period>) -> Self {
+        let periods = periods
+            .into_iter()
+            .zip(1..)
+            .map(|(period, number)| CalendarPeriod {
+                number,
+                id: period.id,
+                name: period.name,
+                start: period.start,
+                end: period.end,
+                working_days: period.working_days,
+                hours: period.hours,
+            })
+            .collect();
+        PeriodCalendar { year, periods }
+    }
+
+    /// Renders the calendar in the given format
+    pub fn render(&self, format: PeriodFormat) -> String {
+        match format {
+            PeriodFormat::Json => serde_json::to_string(self).expect("serializable calendar"),
+            PeriodFormat::Csv => self.to_csv(),
+            PeriodFormat::Ics => self.to_ics(),
+        }
+    }
+
+    /// Renders the calendar as comma-separated values with a header row
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("number,id,name,start,end,working_days,hours\n");
+        for period in &self.periods {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{}",
+                period.number,
+                period.id,
+                period.name,
+                period.start,
+                period.end,
+                period.working_days,
+                period.hours.as_f64()
+            );
+        }
+        csv
+    }
+
+    /// Renders the calendar as an iCalendar feed with an all-day event on the last day
+    /// of each period
+    pub fn to_ics(&self) -> String {
+        let stamp = Utc::now().format("%Y%m%dT%H%M%SZ");
+        let mut ics = String::new();
+        push_ics_line(&mut ics, "BEGIN:VCALENDAR");
+        push_ics_line(&mut ics, "VERSION:2.0");
+        push_ics_line(&mut ics, "PRODID:-//time-tally//Reporting periods//EN");
+        push_ics_line(&mut ics, "CALSCALE:GREGORIAN");
+        for period in &self.periods {
+            let lines = [
+                "BEGIN:VEVENT".to_string(),
+                format!("UID:{}@time-tally", period.id),
+                format!("DTSTAMP:{}", stamp),
+                format!("DTSTART;VALUE=DATE:{}", period.end.format("%Y%m%d")),
+                format!(
+                    "DTEND;VALUE=DATE:{}",
+                    (period.end + Duration::days(1)).format("%Y%m%d")
+                ),
+                format!("SUMMARY:Reporting deadline {}", period.name),
+                format!(
+                    "DESCRIPTION:Period {} of {} from {} to {}: {} hours in {} working days",
+                    period.number,
+                    self.year,
+                    period.start,
+                    period.end,
+                    period.hours.as_f64(),
+                    period.working_days
+                ),
+                "END:VEVENT".to_string(),
+            ];
+            for line in &lines {
+                push_ics_line(&mut ics, line);
+            }
+        }
+        push_ics_line(&mut ics, "END:VCALENDAR");
+        ics
+    }
+}
+
+/// The longest content line of an iCalendar feed in octets, excluding the line break
+const ICS_LINE_OCTETS: usize = 75;
+
+/// Appends a content line to an iCalendar feed, folding it into lines of at most 75 octets
+/// continued by a leading space as RFC 5545 requires, without splitting a character
+fn push_ics_line(ics: &mut String, line: &str) {
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > ICS_LINE_OCTETS {
+            ics.push_str("\r\n ");
+            octets = 1;
+        }
+        ics.push(c);
+        octets += c.len_utf8();
+    }
+    ics.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workhours::{period_calendar, CalculationOptions};
+
+    #[test]
+    fn numbers_every_period_of_the_year() {
+        let calendar = period_calendar(2024, &CalculationOptions::default()).unwrap();
+
+        assert_eq!(calendar.periods[0].number, 1);
+        assert_eq!(
+            calendar.periods[0].start,
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()
+        );
+        assert_eq!(
+            calendar.periods.last().unwrap().end,
+            NaiveDate::from_ymd_opt(2024, 12, 31).unwrap()
+        );
+        for pair in calendar.periods.windows(2) {
+            assert_eq!(pair[0].end + Duration::days(1), pair[1].start);
+            assert_eq!(pair[0].number + 1, pair[1].number);
+            assert!(pair[0].id < pair[1].id);
+        }
+    }
+
+    #[test]
+    fn renders_csv_and_ics() {
+        let calendar = period_calendar(2024, &CalculationOptions::default()).unwrap();
+
+        let csv = calendar.render(PeriodFormat::Csv);
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("number,id,name,start,end,working_days,hours")
+        );
+        assert_eq!(
+            lines.next(),
+            Some("1,2024-01-W01,week: 1,2024-01-01,2024-01-07,4,32")
+        );
+        assert_eq!(lines.count(), calendar.periods.len() - 1);
+
+        let ics = calendar.render(PeriodFormat::Ics);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20240107\r\nDTEND;VALUE=DATE:20240108\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), calendar.periods.len());
+        assert!(ics.split("\r\n").all(|line| line.len() <= 75));
+    }
+
+    #[test]
+    fn folds_long_ics_lines() {
+        let mut ics = String::new();
+        push_ics_line(&mut ics, &format!("DESCRIPTION:{}", "å".repeat(40)));
+        let lines: Vec<&str> = ics.trim_end_matches("\r\n").split("\r\n").collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].len(), 74);
+        assert!(lines[1].starts_with(' '));
+        assert_eq!(lines.concat().replacen(' ', "", 1).len(), 12 + 80);
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("CSV".parse(), Ok(PeriodFormat::Csv));
+        assert_eq!("ics".parse(), Ok(PeriodFormat::Ics));
+        assert!("xml".parse::<PeriodFormat>().is_err());
+    }
+}
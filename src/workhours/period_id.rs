@@ -0,0 +1,171 @@
+//! # Period Identifiers
+//!
+//! This module defines `PeriodId`, a unique identifier of a reporting period that sorts
+//! chronologically, unlike period names such as "week: 1" which repeat at year ends.
+//!
+//! ## Format
+//!
+//! `YYYY-MM-Www`: the year and month the period starts in and the ISO week of the period,
+//! e.g. `2024-12-W01` for the period starting in December 2024 that falls in week 1 of 2025.
+//! No two periods of a scheme starting in the same month share an ISO week, so the format
+//! is unique for every scheme.
+
+use serde::{Deserialize, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// A unique, chronologically sortable identifier of a reporting period
+///
+/// # Example
+///
+/// ```
+/// use time_tally::workhours::PeriodId;
+///
+/// let id: PeriodId = "2024-12-W01".parse().unwrap();
+/// assert_eq!((id.year, id.month, id.week), (2024, 12, 1));
+/// assert_eq!(id.to_string(), "2024-12-W01");
+/// assert!(id > "2024-12-W52".parse().unwrap());
+/// assert!(id < "2025-01-W02".parse().unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub struct PeriodId {
+    /// The year the period starts in
+    pub year: i32,
+    /// The month the period starts in, 1 for January
+    pub month: u32,
+    /// The ISO week of the period
+    pub week: u32,
+}
+
+impl PeriodId {
+    /// Returns the ISO year of the week, which differs from the year for a week 1 starting
+    /// in December or a week 52 or 53 starting in January
+    fn week_year(&self) -> i32 {
+        match (self.month, self.week) {
+            (12, 1) => self.year + 1,
+            (1, 52..) => self.year - 1,
+            _ => self.year,
+        }
+    }
+}
+
+impl Ord for PeriodId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.year, self.month, self.week_year(), self.week).cmp(&(
+            other.year,
+            other.month,
+            other.week_year(),
+            other.week,
+        ))
+    }
+}
+
+impl PartialOrd for PeriodId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for PeriodId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-W{:02}", self.year, self.month, self.week)
+    }
+}
+
+impl FromStr for PeriodId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid period id '{}': expected YYYY-MM-Www", s);
+        let parts: Vec<&str> = s.split('-').collect();
+        let [year, month, week] = parts[..] else {
+            return Err(invalid());
+        };
+
+        let id = PeriodId {
+            year: year.parse().map_err(|_| invalid())?,
+            month: month.parse().map_err(|_| invalid())?,
+            week: week
+                .strip_prefix('W')
+                .and_then(|week| week.parse().ok())
+                .ok_or_else(invalid)?,
+        };
+        let valid =
+            (1..=12).contains(&id.month) && (1..=53).contains(&id.week) && id.to_string() == s;
+        valid.then_some(id).ok_or_else(invalid)
+    }
+}
+
+impl TryFrom<String> for PeriodId {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Serialize for PeriodId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorts_chronologically() {
+        let mut ids: Vec<PeriodId> = [
+            "2025-01-W01",
+            "2024-12-W01",
+            "2024-12-W52",
+            "2024-02-W05",
+            "2027-01-W53",
+            "2027-01-W01",
+        ]
+        .iter()
+        .map(|id| id.parse().unwrap())
+        .collect();
+        ids.sort();
+        let ids: Vec<String> = ids.iter().map(PeriodId::to_string).collect();
+        assert_eq!(
+            ids,
+            [
+                "2024-02-W05",
+                "2024-12-W52",
+                "2024-12-W01",
+                "2025-01-W01",
+                "2027-01-W53",
+                "2027-01-W01"
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_the_id_of_a_calculated_period() {
+        let calculated = PeriodId {
+            year: 2024,
+            month: 6,
+            week: 24,
+        };
+        assert_eq!("2024-06-W24".parse::<PeriodId>(), Ok(calculated));
+    }
+
+    #[test]
+    fn rejects_invalid_ids() {
+        for invalid in [
+            "2024-12-1-W01",
+            "2024-13-W01",
+            "2024-12-W54",
+            "2024-12-W00",
+            "2024-12-01",
+            "2024-1-W01",
+            "2024-12-W1",
+        ] {
+            assert!(invalid.parse::<PeriodId>().is_err(), "{}", invalid);
+        }
+    }
+}
@@ -0,0 +1,50 @@
+//! # Progress
+//!
+//! This module defines `Progress`, the work hours of a span split into the hours already
+//! behind and the hours remaining as of a reference date.
+
+use super::Hours;
+use serde::Serialize;
+use std::ops::AddAssign;
+
+/// The work hours of a span split at an "as of" date
+///
+/// Hours on days before the date are elapsed, while hours on the date itself and later
+/// are remaining.
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use time_tally::workhours::{calculate_periods, CalculationOptions, Hours};
+///
+/// let options = CalculationOptions {
+///     as_of: NaiveDate::from_ymd_opt(2024, 6, 12),
+///     ..Default::default()
+/// };
+/// let periods = calculate_periods("10-06-2024".to_string(), "16-06-2024".to_string(), &options).unwrap();
+/// let progress = periods[0].progress.unwrap();
+/// assert_eq!(progress.elapsed, Hours::from_hours(8 * 2));
+/// assert_eq!(progress.remaining, Hours::from_hours(8 * 3));
+/// ```
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Progress {
+    /// The work hours before the "as of" date
+    pub elapsed: Hours,
+    /// The work hours on and after the "as of" date
+    pub remaining: Hours,
+}
+
+impl AddAssign for Progress {
+    fn add_assign(&mut self, other: Self) {
+        self.elapsed += other.elapsed;
+        self.remaining += other.remaining;
+    }
+}
+
+/// Adds the progress of a part, if any, to the progress of the whole it belongs to
+pub(super) fn accumulate(total: &mut Option<Progress>, part: Option<Progress>) {
+    if let Some(part) = part {
+        *total.get_or_insert_with(Progress::default) += part;
+    }
+}
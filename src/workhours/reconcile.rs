@@ -0,0 +1,368 @@
+//! # Reconciliation
+//!
+//! This module defines `Reconciliation`, a comparison of the hours reported for each
+//! reporting period, e.g. in UBW, against the work hours expected in it, flagging periods
+//! with too few or too many hours reported.
+
+use super::date_input::{parse_single_day, DateInputError};
+use super::{Hours, Period, PeriodId, WorkhoursError};
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+/// The reported hours compared against the expected hours of a range
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Reconciliation {
+    /// The first date of the range
+    pub start: NaiveDate,
+    /// The last date of the range
+    pub end: NaiveDate,
+    /// The periods of the range in date order
+    pub periods: Vec<PeriodReconciliation>,
+    /// The expected work hours across all periods
+    pub expected: Hours,
+    /// The reported hours across all periods
+    pub reported: Hours,
+    /// The reported hours minus the expected hours across all periods
+    pub difference: Hours,
+    /// Whether too few, too many or the expected hours were reported across all periods
+    pub status: ReportingStatus,
+}
+
+/// The reported hours compared against the expected hours of a single period
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct PeriodReconciliation {
+    /// The unique identifier of the period, e.g. "2024-06-W23"
+    pub id: PeriodId,
+    /// The name of the period, e.g. "week: 23"
+    pub name: String,
+    /// The first date of the period within the range
+    pub start: NaiveDate,
+    /// The last date of the period within the range
+    pub end: NaiveDate,
+    /// The expected work hours of the period within the range
+    pub expected: Hours,
+    /// The hours reported for the period and its dates
+    pub reported: Hours,
+    /// The reported hours minus the expected hours
+    pub difference: Hours,
+    /// Whether too few, too many or the expected hours were reported
+    pub status: ReportingStatus,
+}
+
+/// Whether reported hours fall short of, exceed or match the expected hours
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportingStatus {
+    /// Fewer hours were reported than expected
+    Under,
+    /// The expected hours were reported
+    Balanced,
+    /// More hours were reported than expected
+    Over,
+}
+
+impl ReportingStatus {
+    /// Returns the status of a difference between reported and expected hours
+    fn of(difference: Hours) -> Self {
+        match difference.cmp(&Hours::ZERO) {
+            Ordering::Less => ReportingStatus::Under,
+            Ordering::Equal => ReportingStatus::Balanced,
+            Ordering::Greater => ReportingStatus::Over,
+        }
+    }
+}
+
+/// What a key of the reported hours refers to
+enum ReportedKey {
+    /// A whole period
+    Period(PeriodId),
+    /// A single date
+    Date(NaiveDate),
+}
+
+/// Reads a key of the reported hours as a period id or, failing that, a single date
+fn parse_key(key: &str) -> Result<ReportedKey, WorkhoursError> {
+    if let Ok(id) = key.parse() {
+        return Ok(ReportedKey::Period(id));
+    }
+    parse_single_day(key)
+        .map(ReportedKey::Date)
+        .map_err(|err| match err {
+            DateInputError::Invalid => WorkhoursError::InvalidReported(key.to_string()),
+            DateInputError::Ambiguous => WorkhoursError::AmbiguousDate {
+                parameter: "reported",
+                input: key.to_string(),
+            },
+        })
+}
+
+impl Reconciliation {
+    /// Compares the reported hours against the expected hours of each period
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The first date of the range
+    /// * `end` - The last date of the range
+    /// * `periods` - The periods of the range in date order
+    /// * `reported` - Hours reported by period id or by date
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a key of the reported hours is neither a period id nor a date,
+    /// refers to a date outside the range or a period not wholly inside it, or has negative
+    /// hours. Keys for the same date or period are added up, and fail if they add up to more
+    /// than 24 hours for each of its days, as do hours reported for both a period and any
+    /// of its dates
+    pub(super) fn from_periods(
+        start: NaiveDate,
+        end: NaiveDate,
+        periods: Vec<Period>,
+        reported: &BTreeMap<String, Hours>,
+    ) -> Result<Self, WorkhoursError> {
+        //Hours by period for period keys, and by date for date keys, summed over keys
+        //written differently for the same period or date
+        let mut period_hours: BTreeMap<usize, Hours> = BTreeMap::new();
+        let mut date_hours: BTreeMap<NaiveDate, (usize, Hours)> = BTreeMap::new();
+        for (key, hours) in reported {
+            let invalid_hours = |key: String, days: i64| WorkhoursError::InvalidReportedHours {
+                key,
+                max: Hours::from_hours(24 * days),
+            };
+            if *hours < Hours::ZERO {
+                return Err(invalid_hours(key.clone(), 1));
+            }
+            match parse_key(key)? {
+                ReportedKey::Period(id) => {
+                    //A period clipped by the range would compare its whole hours to part of them
+                    let index = periods
+                        .iter()
+                        .position(|period| {
+                            period.id == id
+                                && period.start == period.period_start
+                                && period.end == period.period_end
+                        })
+                        .ok_or_else(|| WorkhoursError::ReportedOutsideRange(key.clone()))?;
+                    let period = &periods[index];
+                    let days = (period.end - period.start).num_days() + 1;
+                    let total = period_hours.entry(index).or_insert(Hours::ZERO);
+                    *total = total
+                        .checked_add(*hours)
+                        .filter(|total| *total <= Hours::from_hours(24 * days))
+                        .ok_or_else(|| invalid_hours(key.clone(), days))?;
+                }
+                ReportedKey::Date(date) => {
+                    let index = periods
+                        .iter()
+                        .position(|period| period.start <= date && date <= period.end)
+                        .ok_or_else(|| WorkhoursError::ReportedOutsideRange(key.clone()))?;
+                    let (_, total) = date_hours.entry(date).or_insert((index, Hours::ZERO));
+                    *total = total
+                        .checked_add(*hours)
+                        .filter(|total| *total <= Hours::from_hours(24))
+                        .ok_or_else(|| invalid_hours(date.to_string(), 1))?;
+                }
+            }
+        }
+
+        let mut reported_hours = vec![Hours::ZERO; periods.len()];
+        for (index, hours) in &period_hours {
+            reported_hours[*index] = *hours;
+        }
+        for (date, (index, hours)) in &date_hours {
+            //Hours of a period already include those of its dates
+            if period_hours.contains_key(index) {
+                return Err(WorkhoursError::ReportedTwice {
+                    period: periods[*index].id.to_string(),
+                    date: date.to_string(),
+                });
+            }
+            reported_hours[*index] += *hours;
+        }
+
+        let periods: Vec<PeriodReconciliation> = periods
+            .into_iter()
+            .zip(reported_hours)
+            .map(|(period, reported)| {
+                let difference = reported - period.hours;
+                PeriodReconciliation {
+                    id: period.id,
+                    name: period.name,
+                    start: period.start,
+                    end: period.end,
+                    expected: period.hours,
+                    reported,
+                    difference,
+                    status: ReportingStatus::of(difference),
+                }
+            })
+            .collect();
+
+        let expected: Hours = periods.iter().map(|period| period.expected).sum();
+        let reported: Hours = periods.iter().map(|period| period.reported).sum();
+        let difference = reported - expected;
+        Ok(Reconciliation {
+            start,
+            end,
+            periods,
+            expected,
+            reported,
+            difference,
+            status: ReportingStatus::of(difference),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workhours::{calculate_periods, CalculationOptions};
+
+    fn reconcile(reported: &[(&str, i64)]) -> Result<Reconciliation, WorkhoursError> {
+        //The last period of May 2024, 27-31 May, and the first of June, 1-9 June
+        reconcile_from("27-05-2024", reported)
+    }
+
+    fn reconcile_from(
+        start: &str,
+        reported: &[(&str, i64)],
+    ) -> Result<Reconciliation, WorkhoursError> {
+        let periods = calculate_periods(
+            start.to_string(),
+            "09-06-2024".to_string(),
+            &CalculationOptions::default(),
+        )
+        .unwrap();
+        let reported = reported
+            .iter()
+            .map(|(key, minutes)| (key.to_string(), Hours::from_minutes(*minutes)))
+            .collect();
+        Reconciliation::from_periods(
+            periods[0].start,
+            periods[periods.len() - 1].end,
+            periods,
+            &reported,
+        )
+    }
+
+    #[test]
+    fn flags_under_and_over_reporting() {
+        let reconciliation = reconcile(&[
+            ("2024-05-W22", 40 * 60),
+            ("2024-06-03", 8 * 60),
+            ("04-06-2024", 8 * 60),
+            ("20240605", 10 * 60),
+        ])
+        .unwrap();
+
+        let statuses: Vec<_> = reconciliation
+            .periods
+            .iter()
+            .map(|period| (period.id.to_string(), period.difference, period.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (
+                    "2024-05-W22".to_string(),
+                    Hours::ZERO,
+                    ReportingStatus::Balanced
+                ),
+                (
+                    "2024-06-W23".to_string(),
+                    Hours::from_hours(-6),
+                    ReportingStatus::Under
+                ),
+            ]
+        );
+        assert_eq!(reconciliation.expected, Hours::from_hours(72));
+        assert_eq!(reconciliation.reported, Hours::from_hours(66));
+        assert_eq!(reconciliation.status, ReportingStatus::Under);
+    }
+
+    #[test]
+    fn rejects_keys_outside_the_range() {
+        assert_eq!(
+            reconcile(&[("2024-06-10", 60)]),
+            Err(WorkhoursError::ReportedOutsideRange(
+                "2024-06-10".to_string()
+            ))
+        );
+        assert_eq!(
+            reconcile(&[("2024-W23", 60)]),
+            Err(WorkhoursError::InvalidReported("2024-W23".to_string()))
+        );
+        assert_eq!(
+            reconcile(&[("03/06/2024", 60)]).unwrap_err().code(),
+            "ambiguous_date"
+        );
+    }
+
+    #[test]
+    fn rejects_periods_clipped_by_the_range() {
+        assert_eq!(
+            reconcile_from("05-06-2024", &[("2024-06-W23", 40 * 60)]),
+            Err(WorkhoursError::ReportedOutsideRange(
+                "2024-06-W23".to_string()
+            ))
+        );
+        assert!(reconcile_from("05-06-2024", &[("2024-06-05", 8 * 60)]).is_ok());
+    }
+
+    #[test]
+    fn rejects_negative_and_implausible_hours() {
+        assert_eq!(
+            reconcile(&[("2024-06-03", -40 * 60)]),
+            Err(WorkhoursError::InvalidReportedHours {
+                key: "2024-06-03".to_string(),
+                max: Hours::from_hours(24),
+            })
+        );
+        assert_eq!(
+            reconcile(&[("2024-06-03", 24 * 60 + 1)])
+                .unwrap_err()
+                .code(),
+            "invalid_reported_hours"
+        );
+        //1-9 June has nine days
+        assert!(reconcile(&[("2024-06-W23", 9 * 24 * 60)]).is_ok());
+        assert_eq!(
+            reconcile(&[("2024-06-W23", 9 * 24 * 60 + 1)]),
+            Err(WorkhoursError::InvalidReportedHours {
+                key: "2024-06-W23".to_string(),
+                max: Hours::from_hours(9 * 24),
+            })
+        );
+    }
+
+    #[test]
+    fn sums_keys_of_the_same_date() {
+        let reconciliation = reconcile(&[("2024-06-03", 4 * 60), ("03-06-2024", 4 * 60)]).unwrap();
+        assert_eq!(reconciliation.periods[1].reported, Hours::from_hours(8));
+
+        assert_eq!(
+            reconcile(&[
+                ("2024-06-03", 24 * 60),
+                ("03-06-2024", 24 * 60),
+                ("20240603", 24 * 60),
+            ]),
+            Err(WorkhoursError::InvalidReportedHours {
+                key: "2024-06-03".to_string(),
+                max: Hours::from_hours(24),
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_a_period_reported_with_its_dates() {
+        assert_eq!(
+            reconcile(&[("2024-06-W23", 40 * 60), ("20240603", 8 * 60)]),
+            Err(WorkhoursError::ReportedTwice {
+                period: "2024-06-W23".to_string(),
+                date: "2024-06-03".to_string(),
+            })
+        );
+        assert!(reconcile(&[("2024-05-W22", 40 * 60), ("2024-06-03", 8 * 60)]).is_ok());
+    }
+}
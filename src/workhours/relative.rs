@@ -0,0 +1,321 @@
+//! # Relative Dates
+//!
+//! This module resolves date expressions relative to today's date:
+//!
+//! - `today`, `yesterday` and `tomorrow`
+//! - `start-of-UNIT` and `end-of-UNIT`, where `UNIT` is `week`, `month`, `year` or `period`,
+//!   e.g. `end-of-month`
+//! - `this-UNIT`, `next-UNIT` and `previous-UNIT`, which are the first day of the unit as the
+//!   start of a range and its last day as the end, e.g. `start=next-period&end=next-period`
+//! - An offset of days, weeks, months or years, e.g. `+2w` or `-1m`, either on its own from
+//!   today or after another expression, e.g. `end-of-month-1d`
+//!
+//! Periods are the periods of the calculation's `PeriodScheme`.
+
+use super::{DateBound, PeriodScheme, WorkhoursError};
+use chrono::{Datelike, Duration, Months, NaiveDate};
+
+/// A span of days that relative expressions refer to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Week,
+    Month,
+    Year,
+    Period,
+}
+
+/// Which day of a unit an expression refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edge {
+    /// The first day, as in "start-of-month"
+    Start,
+    /// The last day, as in "end-of-month"
+    End,
+    /// The first day as the start of a range and the last day as the end, as in "this-month"
+    Bound,
+}
+
+/// Resolves a relative date expression
+///
+/// # Arguments
+///
+/// * `input` - The expression, e.g. "today", "end-of-period" or "+2w"
+/// * `bound` - Whether the date starts or ends a range, which decides the day that
+///   expressions such as "this-month" resolve to
+/// * `today` - Today's date
+/// * `scheme` - The period scheme that "period" expressions refer to
+///
+/// # Returns
+///
+/// A `Result` containing the date, or `None` if the input is not a relative expression,
+/// e.g. an absolute date, or resolves to a date out of range
+///
+/// # Errors
+///
+/// Returns an error if there's an issue calculating period boundaries
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use time_tally::workhours::{resolve_relative_date, DateBound, PeriodScheme};
+///
+/// let today = NaiveDate::from_ymd_opt(2024, 6, 5).unwrap();
+/// let resolve = |input| resolve_relative_date(input, DateBound::End, today, &PeriodScheme::default());
+/// assert_eq!(resolve("end-of-month").unwrap(), NaiveDate::from_ymd_opt(2024, 6, 30));
+/// assert_eq!(resolve("this-period").unwrap(), NaiveDate::from_ymd_opt(2024, 6, 9));
+/// assert_eq!(resolve("+2w").unwrap(), NaiveDate::from_ymd_opt(2024, 6, 19));
+/// assert_eq!(resolve("05-06-2024").unwrap(), None);
+/// ```
+pub fn resolve_relative_date(
+    input: &str,
+    bound: DateBound,
+    today: NaiveDate,
+    scheme: &PeriodScheme,
+) -> Result<Option<NaiveDate>, WorkhoursError> {
+    let input = input.trim().to_ascii_lowercase();
+    let (expression, offset) = split_offset(&input);
+
+    let date = match expression {
+        "" if offset.is_some() => Some(today),
+        "today" => Some(today),
+        "yesterday" => today.pred_opt(),
+        "tomorrow" => today.succ_opt(),
+        _ => match parse_span(expression) {
+            Some((edge, unit, shift)) => {
+                let edge = match (edge, bound) {
+                    (Edge::Bound, DateBound::Start) => Edge::Start,
+                    (Edge::Bound, DateBound::End) => Edge::End,
+                    (edge, _) => edge,
+                };
+                span_edge(today, edge, unit, shift, scheme)?
+            }
+            None => return Ok(None),
+        },
+    };
+    Ok(match offset {
+        Some((amount, unit)) => date.and_then(|date| apply_offset(date, amount, unit)),
+        None => date,
+    })
+}
+
+/// Splits a trailing offset such as "+2w" or "-1d" from an expression
+///
+/// A missing sign is read as a plus, and so is a space, which is what a plus becomes when
+/// it is not encoded in a query string.
+fn split_offset(input: &str) -> (&str, Option<(i64, char)>) {
+    let Some(unit) = input.chars().last().filter(|unit| "dwmy".contains(*unit)) else {
+        return (input, None);
+    };
+    let number = &input[..input.len() - 1];
+    let digits = number.len() - number.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    if digits == 0 {
+        return (input, None);
+    }
+    let Ok(amount) = number[number.len() - digits..].parse::<i64>() else {
+        return (input, None);
+    };
+    let rest = &number[..number.len() - digits];
+    match rest.chars().last() {
+        None => ("", Some((amount, unit))),
+        Some('+') | Some(' ') => (&rest[..rest.len() - 1], Some((amount, unit))),
+        Some('-') => (&rest[..rest.len() - 1], Some((-amount, unit))),
+        Some(_) => (input, None),
+    }
+}
+
+/// Parses expressions such as "start-of-month" or "next-period" into the edge of the unit
+/// and the number of units from the one containing today
+fn parse_span(expression: &str) -> Option<(Edge, Unit, i32)> {
+    let (edge, shift, unit) = if let Some(unit) = expression.strip_prefix("start-of-") {
+        (Edge::Start, 0, unit)
+    } else if let Some(unit) = expression.strip_prefix("end-of-") {
+        (Edge::End, 0, unit)
+    } else if let Some(unit) = expression.strip_prefix("this-") {
+        (Edge::Bound, 0, unit)
+    } else if let Some(unit) = expression.strip_prefix("next-") {
+        (Edge::Bound, 1, unit)
+    } else if let Some(unit) = expression.strip_prefix("previous-") {
+        (Edge::Bound, -1, unit)
+    } else {
+        return None;
+    };
+    let unit = match unit {
+        "week" => Unit::Week,
+        "month" => Unit::Month,
+        "year" => Unit::Year,
+        "period" => Unit::Period,
+        _ => return None,
+    };
+    Some((edge, unit, shift))
+}
+
+/// Returns the first or last day of the unit `shift` units away from the one containing `date`
+fn span_edge(
+    date: NaiveDate,
+    edge: Edge,
+    unit: Unit,
+    shift: i32,
+    scheme: &PeriodScheme,
+) -> Result<Option<NaiveDate>, WorkhoursError> {
+    let Some((mut start, mut end)) = span(date, unit, scheme)? else {
+        return Ok(None);
+    };
+    for _ in 0..shift.abs() {
+        let next = if shift > 0 {
+            end.succ_opt()
+        } else {
+            start.pred_opt()
+        };
+        let Some(next) = next else {
+            return Ok(None);
+        };
+        match span(next, unit, scheme)? {
+            Some(span) => (start, end) = span,
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(if edge == Edge::End { end } else { start }))
+}
+
+/// Returns the first and last days of the unit containing a date
+fn span(
+    date: NaiveDate,
+    unit: Unit,
+    scheme: &PeriodScheme,
+) -> Result<Option<(NaiveDate, NaiveDate)>, WorkhoursError> {
+    let span = match unit {
+        Unit::Week => {
+            let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+            Some((monday, monday + Duration::days(6)))
+        }
+        Unit::Month => date.with_day(1).and_then(|first| {
+            let next = first.checked_add_months(Months::new(1))?;
+            Some((first, next.pred_opt()?))
+        }),
+        Unit::Year => NaiveDate::from_ymd_opt(date.year(), 1, 1).zip(NaiveDate::from_ymd_opt(
+            date.year(),
+            12,
+            31,
+        )),
+        Unit::Period => Some(scheme.boundaries(&date).map_err(WorkhoursError::Calendar)?),
+    };
+    Ok(span)
+}
+
+/// Moves a date by a number of days, weeks, months or years
+fn apply_offset(date: NaiveDate, amount: i64, unit: char) -> Option<NaiveDate> {
+    let months = |months: i64| {
+        let count = Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+        if months < 0 {
+            date.checked_sub_months(count)
+        } else {
+            date.checked_add_months(count)
+        }
+    };
+    match unit {
+        'd' => date.checked_add_signed(Duration::try_days(amount)?),
+        'w' => date.checked_add_signed(Duration::try_weeks(amount)?),
+        'm' => months(amount),
+        'y' => months(amount.checked_mul(12)?),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn resolve(input: &str, bound: DateBound) -> Option<NaiveDate> {
+        //Wednesday 5 June 2024, in UBW week 23 from 1 to 9 June
+        resolve_relative_date(input, bound, date(2024, 6, 5), &PeriodScheme::default()).unwrap()
+    }
+
+    #[test]
+    fn days() {
+        assert_eq!(resolve("today", DateBound::Start), Some(date(2024, 6, 5)));
+        assert_eq!(
+            resolve("Yesterday", DateBound::Start),
+            Some(date(2024, 6, 4))
+        );
+        assert_eq!(resolve("tomorrow", DateBound::End), Some(date(2024, 6, 6)));
+    }
+
+    #[test]
+    fn units() {
+        assert_eq!(
+            resolve("start-of-week", DateBound::End),
+            Some(date(2024, 6, 3))
+        );
+        assert_eq!(
+            resolve("end-of-year", DateBound::Start),
+            Some(date(2024, 12, 31))
+        );
+        assert_eq!(
+            resolve("start-of-period", DateBound::End),
+            Some(date(2024, 6, 1))
+        );
+        assert_eq!(
+            resolve("this-month", DateBound::Start),
+            Some(date(2024, 6, 1))
+        );
+        assert_eq!(
+            resolve("this-month", DateBound::End),
+            Some(date(2024, 6, 30))
+        );
+        assert_eq!(
+            resolve("next-period", DateBound::Start),
+            Some(date(2024, 6, 10))
+        );
+        assert_eq!(
+            resolve("next-period", DateBound::End),
+            Some(date(2024, 6, 16))
+        );
+        assert_eq!(
+            resolve("previous-period", DateBound::Start),
+            Some(date(2024, 5, 27))
+        );
+        assert_eq!(
+            resolve("previous-month", DateBound::End),
+            Some(date(2024, 5, 31))
+        );
+    }
+
+    #[test]
+    fn offsets() {
+        assert_eq!(resolve("+2w", DateBound::Start), Some(date(2024, 6, 19)));
+        assert_eq!(resolve(" 2w", DateBound::Start), Some(date(2024, 6, 19)));
+        assert_eq!(resolve("-3d", DateBound::Start), Some(date(2024, 6, 2)));
+        assert_eq!(
+            resolve("today+1m", DateBound::Start),
+            Some(date(2024, 7, 5))
+        );
+        assert_eq!(
+            resolve("end-of-month-1d", DateBound::End),
+            Some(date(2024, 6, 29))
+        );
+        assert_eq!(
+            resolve("start-of-year+1y", DateBound::Start),
+            Some(date(2025, 1, 1))
+        );
+        assert_eq!(resolve("+99999999999y", DateBound::Start), None);
+    }
+
+    #[test]
+    fn absolute_dates_are_not_relative() {
+        for input in [
+            "05-06-2024",
+            "2024-06-05",
+            "20240605",
+            "2024-W23",
+            "end-of-day",
+            "2x",
+        ] {
+            assert_eq!(resolve(input, DateBound::Start), None, "{}", input);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! # Work Hours Reports
+//!
+//! This module defines `WorkHoursReport`, a structured form of the calculated work hours
+//! with ordered arrays of years, months and periods and explicit numeric fields, for
+//! clients that deserialize the response into typed structures, and `PeriodLookup`,
+//! describing the reporting period of a single date.
+
+use super::{absence, progress, AbsenceHours, Hours, Period, Progress};
+use chrono::{Datelike, NaiveDate};
+use serde::Serialize;
+
+/// The calculated work hours for a given time range, ordered by date
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct WorkHoursReport {
+    /// The first date of the calculated range
+    pub start: NaiveDate,
+    /// The last date of the calculated range
+    pub end: NaiveDate,
+    /// The years in the range
+    pub years: Vec<YearReport>,
+    /// Total work hours across all periods
+    pub total: Hours,
+    /// Elapsed and remaining work hours across all periods, if calculated as of a date
+    #[serde(flatten)]
+    pub progress: Option<Progress>,
+    /// Hours of absence by type across all periods, if any absences were given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub absence: Option<AbsenceHours>,
+}
+
+/// The work hours of a single year
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct YearReport {
+    /// The year, e.g. 2024
+    pub year: i32,
+    /// The months of the year within the range
+    pub months: Vec<MonthReport>,
+    /// Total work hours for the year
+    pub total: Hours,
+    /// Elapsed and remaining work hours for the year, if calculated as of a date
+    #[serde(flatten)]
+    pub progress: Option<Progress>,
+    /// Hours of absence by type for the year, if any absences were given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub absence: Option<AbsenceHours>,
+}
+
+/// The work hours of a single month
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct MonthReport {
+    /// The number of the month, 1 for January
+    pub month: u32,
+    /// The English name of the month, e.g. "January"
+    pub name: String,
+    /// The periods starting in the month within the range
+    pub periods: Vec<PeriodReport>,
+    /// Total work hours for the month
+    pub total: Hours,
+    /// Elapsed and remaining work hours for the month, if calculated as of a date
+    #[serde(flatten)]
+    pub progress: Option<Progress>,
+    /// Hours of absence by type for the month, if any absences were given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub absence: Option<AbsenceHours>,
+}
+
+/// The work hours of a single reporting period
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct PeriodReport {
+    /// The position of the period among all the periods starting in its month, starting
+    /// at 1, including any before the start of the range
+    pub index: u32,
+    /// The name of the period, e.g. "week: 23"
+    pub name: String,
+    /// The dates, working days and work hours of the period
+    #[serde(flatten)]
+    pub period: Period,
+}
+
+impl WorkHoursReport {
+    /// Groups periods by year and month
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The first date of the calculated range
+    /// * `end` - The last date of the calculated range
+    /// * `periods` - The periods in date order
+    pub(super) fn from_periods(start: NaiveDate, end: NaiveDate, periods: Vec<Period>) -> Self {
+        let mut years: Vec<YearReport> = Vec::new();
+
+        for period in periods {
+            let date = period.start;
+            if years.last().map(|year| year.year) != Some(date.year()) {
+                years.push(YearReport {
+                    year: date.year(),
+                    months: Vec::new(),
+                    total: Hours::ZERO,
+                    progress: None,
+                    absence: None,
+                });
+            }
+            let year = years.last_mut().expect("year was just added");
+
+            if year.months.last().map(|month| month.month) != Some(date.month()) {
+                year.months.push(MonthReport {
+                    month: date.month(),
+                    name: date.format("%B").to_string(),
+                    periods: Vec::new(),
+                    total: Hours::ZERO,
+                    progress: None,
+                    absence: None,
+                });
+            }
+            let month = year.months.last_mut().expect("month was just added");
+
+            year.total += period.hours;
+            month.total += period.hours;
+            progress::accumulate(&mut year.progress, period.progress);
+            progress::accumulate(&mut month.progress, period.progress);
+            absence::accumulate(&mut year.absence, period.absence.as_ref());
+            absence::accumulate(&mut month.absence, period.absence.as_ref());
+            month.periods.push(PeriodReport {
+                index: period.sequence,
+                name: period.name.clone(),
+                period,
+            });
+        }
+
+        let mut total_progress = None;
+        let mut total_absence = None;
+        for year in &years {
+            progress::accumulate(&mut total_progress, year.progress);
+            absence::accumulate(&mut total_absence, year.absence.as_ref());
+        }
+        WorkHoursReport {
+            start,
+            end,
+            total: years.iter().map(|year| year.total).sum(),
+            progress: total_progress,
+            absence: total_absence,
+            years,
+        }
+    }
+}
+
+/// A whole reporting period with the year and month it belongs to
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct PeriodSummary {
+    /// The name of the period, e.g. "week: 23"
+    pub name: String,
+    /// The year the period belongs to
+    pub year: i32,
+    /// The number of the month the period belongs to, 1 for January
+    pub month: u32,
+    /// The dates, working days and work hours of the period
+    #[serde(flatten)]
+    pub period: Period,
+}
+
+impl From<Period> for PeriodSummary {
+    fn from(period: Period) -> Self {
+        PeriodSummary {
+            name: period.name.clone(),
+            year: period.period_start.year(),
+            month: period.period_start.month(),
+            period,
+        }
+    }
+}
+
+/// The reporting period containing a date, with the periods before and after it
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct PeriodLookup {
+    /// The period containing the date
+    #[serde(flatten)]
+    pub period: PeriodSummary,
+    /// The period ending the day before the period starts
+    pub previous: PeriodSummary,
+    /// The period starting the day after the period ends
+    pub next: PeriodSummary,
+}
@@ -0,0 +1,409 @@
+//! # Work Schedules
+//!
+//! This module defines the `WorkSchedule` type, describing the normal work time on
+//! each day of the week, and the `EmploymentRate` ("sysselsättningsgrad") that scales
+//! it for part-time employees. A `SchedulePlan` switches between schedules over date
+//! ranges, e.g. for shorter summer hours ("sommartid").
+//!
+//! ## Format
+//!
+//! A schedule is written as seven comma-separated values from Monday to Sunday. Each
+//! value is either hours (`8`, `7.5`) or hours and minutes (`7:30`), e.g.
+//! `8,8,8,8,6,0,0` for a shorter Friday.
+
+use super::Hours;
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// How scaled work time is rounded
+///
+/// Work time is rounded to the nearest step, with halves rounded up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Rounding {
+    /// Round to the nearest minute
+    #[default]
+    Minute,
+    /// Round to the nearest quarter of an hour
+    #[serde(alias = "quarter_hour")]
+    Quarter,
+}
+
+impl Rounding {
+    /// Rounds a number of minutes to the nearest step
+    fn round(&self, minutes: f64) -> i64 {
+        let step = match self {
+            Rounding::Minute => 1.0,
+            Rounding::Quarter => 15.0,
+        };
+        ((minutes / step).round() * step) as i64
+    }
+}
+
+/// The employment rate ("sysselsättningsgrad") in percent, scaling the daily work time
+///
+/// The default rate is 100%.
+///
+/// # Example
+///
+/// ```
+/// use time_tally::workhours::{EmploymentRate, Hours, Rounding};
+///
+/// let rate = EmploymentRate::new(80.0).unwrap();
+/// assert_eq!(rate.scale(Hours::from_hours(8), Rounding::Minute), Hours::from_minutes(384));
+/// assert_eq!(rate.scale(Hours::from_hours(8), Rounding::Quarter), Hours::from_minutes(390));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EmploymentRate(f64);
+
+impl EmploymentRate {
+    /// Full-time employment
+    pub const FULL_TIME: EmploymentRate = EmploymentRate(100.0);
+
+    /// Creates an employment rate
+    ///
+    /// # Arguments
+    ///
+    /// * `percent` - The employment rate in percent, e.g. `80.0`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rate is not above 0 and at most 100 percent
+    pub fn new(percent: f64) -> Result<Self, String> {
+        if percent > 0.0 && percent <= 100.0 {
+            Ok(EmploymentRate(percent))
+        } else {
+            Err(format!(
+                "Invalid employment rate {}: must be above 0 and at most 100 percent",
+                percent
+            ))
+        }
+    }
+
+    /// Returns the employment rate in percent
+    pub fn percent(&self) -> f64 {
+        self.0
+    }
+
+    /// Scales the work time of a day by the employment rate
+    ///
+    /// # Arguments
+    ///
+    /// * `hours` - The full-time work time of the day
+    /// * `rounding` - How to round the scaled work time
+    pub fn scale(&self, hours: Hours, rounding: Rounding) -> Hours {
+        if *self == EmploymentRate::FULL_TIME {
+            return hours;
+        }
+        Hours::from_minutes(rounding.round(hours.minutes() as f64 * self.0 / 100.0))
+    }
+}
+
+impl Default for EmploymentRate {
+    fn default() -> Self {
+        EmploymentRate::FULL_TIME
+    }
+}
+
+/// The normal work time on each day of the week
+///
+/// The default schedule is 8 hours Monday to Friday.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{Datelike, NaiveDate, Weekday};
+/// use time_tally::workhours::{Hours, WorkSchedule};
+///
+/// let schedule: WorkSchedule = "8,8,8,8,7:30,0,0".parse().unwrap();
+/// assert_eq!(schedule.hours_on(Weekday::Fri), Hours::from_minutes(450));
+/// assert_eq!(schedule.weekly_hours(), Hours::from_minutes(2370));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct WorkSchedule {
+    /// Work time in minutes from Monday to Sunday
+    minutes: [u32; 7],
+}
+
+impl WorkSchedule {
+    /// Creates a schedule from the whole hours of each day, Monday to Sunday
+    pub fn from_hours(hours: [u32; 7]) -> Self {
+        WorkSchedule {
+            minutes: hours.map(|hours| hours * 60),
+        }
+    }
+
+    /// Creates a schedule from the minutes of each day, Monday to Sunday
+    pub fn from_minutes(minutes: [u32; 7]) -> Self {
+        WorkSchedule { minutes }
+    }
+
+    /// Returns the normal work time on a day of the week
+    pub fn hours_on(&self, weekday: Weekday) -> Hours {
+        Hours::from_minutes(self.minutes[weekday.num_days_from_monday() as usize] as i64)
+    }
+
+    /// Returns the normal work time of a full week
+    pub fn weekly_hours(&self) -> Hours {
+        Hours::from_minutes(self.minutes.iter().map(|minutes| *minutes as i64).sum())
+    }
+}
+
+impl Default for WorkSchedule {
+    fn default() -> Self {
+        WorkSchedule::from_hours([8, 8, 8, 8, 8, 0, 0])
+    }
+}
+
+impl FromStr for WorkSchedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let days: Vec<&str> = s.split(',').map(str::trim).collect();
+        if days.len() != 7 {
+            return Err(format!(
+                "Invalid schedule '{}': expected 7 comma-separated days, Monday to Sunday",
+                s
+            ));
+        }
+
+        let mut minutes = [0; 7];
+        for (day, value) in minutes.iter_mut().zip(days) {
+            *day = parse_day(value)
+                .ok_or_else(|| format!("Invalid schedule '{}': invalid day '{}'", s, value))?;
+        }
+        Ok(WorkSchedule { minutes })
+    }
+}
+
+impl TryFrom<String> for WorkSchedule {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// A work schedule valid between two dates (inclusive)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduleSegment {
+    /// The first date the schedule applies to
+    pub from: NaiveDate,
+    /// The last date the schedule applies to
+    pub to: NaiveDate,
+    /// The schedule to use between the dates
+    pub schedule: WorkSchedule,
+}
+
+/// A default work schedule with date-ranged overrides
+///
+/// The schedule of a day is that of the last segment containing the day, or the default
+/// schedule if no segment contains it.
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use time_tally::workhours::{Hours, SchedulePlan, WorkSchedule};
+///
+/// let midsummer = NaiveDate::from_ymd_opt(2024, 6, 24).unwrap();
+/// let mid_august = NaiveDate::from_ymd_opt(2024, 8, 15).unwrap();
+/// let plan = SchedulePlan::new(WorkSchedule::default())
+///     .with_segment(midsummer, mid_august, "7,7,7,7,7,0,0".parse().unwrap())
+///     .unwrap();
+///
+/// let july = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+/// assert_eq!(plan.hours_on(july), Hours::from_hours(7));
+/// let september = NaiveDate::from_ymd_opt(2024, 9, 2).unwrap();
+/// assert_eq!(plan.hours_on(september), Hours::from_hours(8));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchedulePlan {
+    /// The schedule used outside of all segments
+    default: WorkSchedule,
+    /// Date-ranged schedules, later segments taking precedence
+    segments: Vec<ScheduleSegment>,
+}
+
+impl SchedulePlan {
+    /// Creates a plan using the given schedule on every date
+    pub fn new(default: WorkSchedule) -> Self {
+        SchedulePlan {
+            default,
+            segments: Vec::new(),
+        }
+    }
+
+    /// Adds a schedule used between two dates (inclusive)
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The first date the schedule applies to
+    /// * `to` - The last date the schedule applies to
+    /// * `schedule` - The schedule to use between the dates
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `from` is after `to`
+    pub fn with_segment(
+        mut self,
+        from: NaiveDate,
+        to: NaiveDate,
+        schedule: WorkSchedule,
+    ) -> Result<Self, String> {
+        if from > to {
+            return Err(format!(
+                "Invalid schedule segment: {} is after {}",
+                from.format("%d-%m-%Y"),
+                to.format("%d-%m-%Y")
+            ));
+        }
+        self.segments.push(ScheduleSegment { from, to, schedule });
+        Ok(self)
+    }
+
+    /// Returns the segments of the plan, in order of increasing precedence
+    pub fn segments(&self) -> &[ScheduleSegment] {
+        &self.segments
+    }
+
+    /// Returns the schedule in effect on a date
+    pub fn schedule_on(&self, date: NaiveDate) -> &WorkSchedule {
+        self.segments
+            .iter()
+            .rev()
+            .find(|segment| segment.from <= date && date <= segment.to)
+            .map_or(&self.default, |segment| &segment.schedule)
+    }
+
+    /// Returns the normal work time on a date
+    pub fn hours_on(&self, date: NaiveDate) -> Hours {
+        self.schedule_on(date).hours_on(date.weekday())
+    }
+}
+
+impl From<WorkSchedule> for SchedulePlan {
+    fn from(schedule: WorkSchedule) -> Self {
+        SchedulePlan::new(schedule)
+    }
+}
+
+/// Parses the work time of a single day, either as hours ("7.5") or hours and minutes ("7:30")
+///
+/// Returns `None` if the value is malformed or outside 0 to 24 hours.
+pub(super) fn parse_day(value: &str) -> Option<u32> {
+    let minutes = match value.split_once(':') {
+        Some((hours, minutes)) => {
+            let minutes: u32 = minutes.parse().ok().filter(|minutes| *minutes < 60)?;
+            hours
+                .parse::<u32>()
+                .ok()?
+                .checked_mul(60)?
+                .checked_add(minutes)?
+        }
+        None => {
+            let hours: f64 = value.parse().ok().filter(|hours: &f64| hours.is_finite())?;
+            if hours < 0.0 {
+                return None;
+            }
+            (hours * 60.0).round() as u32
+        }
+    };
+    (minutes <= 24 * 60).then_some(minutes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hours_and_minutes() {
+        let schedule: WorkSchedule = "8, 8, 7.5, 7:45, 6, 0, 0".parse().unwrap();
+        assert_eq!(
+            schedule,
+            WorkSchedule::from_minutes([480, 480, 450, 465, 360, 0, 0])
+        );
+    }
+
+    #[test]
+    fn default_is_forty_hours() {
+        assert_eq!(
+            WorkSchedule::default().weekly_hours(),
+            Hours::from_hours(40)
+        );
+        assert_eq!(WorkSchedule::default().hours_on(Weekday::Sat), Hours::ZERO);
+    }
+
+    #[test]
+    fn employment_rate_rounding() {
+        let half = EmploymentRate::new(50.0).unwrap();
+        assert_eq!(
+            half.scale(Hours::from_minutes(465), Rounding::Minute),
+            Hours::from_minutes(233)
+        );
+        assert_eq!(
+            half.scale(Hours::from_minutes(465), Rounding::Quarter),
+            Hours::from_minutes(240)
+        );
+        assert_eq!(
+            EmploymentRate::default().scale(Hours::from_minutes(465), Rounding::Quarter),
+            Hours::from_minutes(465)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_employment_rates() {
+        for invalid in [0.0, -10.0, 100.5, f64::NAN] {
+            assert!(EmploymentRate::new(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn later_segments_take_precedence() {
+        let date = |day| NaiveDate::from_ymd_opt(2024, 7, day).unwrap();
+        let plan = SchedulePlan::default()
+            .with_segment(
+                date(1),
+                date(31),
+                WorkSchedule::from_hours([7, 7, 7, 7, 7, 0, 0]),
+            )
+            .unwrap()
+            .with_segment(date(8), date(14), WorkSchedule::from_hours([0; 7]))
+            .unwrap();
+
+        assert_eq!(plan.hours_on(date(5)), Hours::from_hours(7));
+        assert_eq!(plan.hours_on(date(10)), Hours::ZERO);
+        assert_eq!(plan.hours_on(date(15)), Hours::from_hours(7));
+        assert_eq!(
+            plan.hours_on(NaiveDate::from_ymd_opt(2024, 8, 1).unwrap()),
+            Hours::from_hours(8)
+        );
+        assert!(SchedulePlan::default()
+            .with_segment(date(2), date(1), WorkSchedule::default())
+            .is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_schedules() {
+        for invalid in [
+            "8,8,8,8,8",
+            "8,8,8,8,8,0,0,0",
+            "8,8,x,8,8,0,0",
+            "25,8,8,8,8,0,0",
+            "-1,8,8,8,8,0,0",
+            "7:60,8,8,8,8,0,0",
+            "71582789:00,8,8,8,8,0,0",
+        ] {
+            assert!(invalid.parse::<WorkSchedule>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn rejects_oversized_hours_and_minutes() {
+        assert_eq!(parse_day("71582789:00"), None);
+        assert_eq!(parse_day(&format!("{}:00", u32::MAX)), None);
+        assert_eq!(parse_day("24:00"), Some(24 * 60));
+    }
+}
@@ -0,0 +1,671 @@
+//! # Period Schemes
+//!
+//! This module defines `PeriodScheme`, the rule splitting a date range into reporting
+//! periods. The default is the UBW scheme of weeks adjusted so that a month break never
+//! splits a period; the others match systems with plain weeks, months, sprints or
+//! fiscal periods.
+//!
+//! How short weeks at month breaks are merged into their neighbours in the UBW scheme is
+//! configured by `UbwRules`, as it differs between UBW installations.
+
+use super::{period_name, NaiveDateExt};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// The ISO week a 4-4-5 fiscal year starts in unless another is given, making the fiscal
+/// year the ISO year
+const DEFAULT_FISCAL_START_WEEK: u32 = 1;
+
+/// Which short weeks at month breaks the UBW scheme merges into a neighbouring week
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeDirection {
+    /// Merge short weeks at the start of a month forward and short weeks at the end backward
+    #[default]
+    Both,
+    /// Only merge short weeks at the start of a month forward into the following week
+    Forward,
+    /// Only merge short weeks at the end of a month backward into the preceding week
+    Backward,
+    /// Never merge, so that every week is split at month breaks
+    None,
+}
+
+/// The rules of the UBW scheme for short weeks at month breaks
+///
+/// A week split by a month break leaves a short week at the start of the month (leading)
+/// and one at the end (trailing). Short weeks of at most the given number of days are merged
+/// into the neighbouring full week of the same month. The default rules merge leading weeks
+/// of up to 4 days and trailing weeks of up to 2 days, giving periods of 3 to 11 days.
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use time_tally::workhours::UbwRules;
+///
+/// let rules = UbwRules { trailing_merge_days: 0, min_length: 1, ..Default::default() };
+/// rules.validate().unwrap();
+///
+/// // Tuesday 30 April 2024 is a period of its own instead of ending week 17
+/// let date = NaiveDate::from_ymd_opt(2024, 4, 30).unwrap();
+/// let (start, _) = rules.boundaries(&date).unwrap();
+/// assert_eq!(start, NaiveDate::from_ymd_opt(2024, 4, 29).unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct UbwRules {
+    /// Short weeks at the start of a month of at most this many days are merged forward
+    pub leading_merge_days: u32,
+    /// Short weeks at the end of a month of at most this many days are merged backward
+    pub trailing_merge_days: u32,
+    /// Which short weeks are merged at all
+    pub direction: MergeDirection,
+    /// The declared minimum length of a period in days
+    pub min_length: u32,
+    /// The declared maximum length of a period in days
+    pub max_length: u32,
+}
+
+impl Default for UbwRules {
+    fn default() -> Self {
+        UbwRules {
+            leading_merge_days: 4,
+            trailing_merge_days: 2,
+            direction: MergeDirection::Both,
+            min_length: 3,
+            max_length: 11,
+        }
+    }
+}
+
+impl UbwRules {
+    /// Checks that every period the rules can produce is within the declared lengths
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a merge threshold is not below 7 days, or if a merged week or a
+    /// short week too long to merge can fall outside `min_length` to `max_length` days
+    pub fn validate(&self) -> Result<(), String> {
+        if self.leading_merge_days > 6 || self.trailing_merge_days > 6 {
+            return Err("UBW merge thresholds must be between 0 and 6 days".to_string());
+        }
+        let (leading, trailing) = (self.leading_days(), self.trailing_days());
+        let shortest = (leading + 1).min(trailing + 1);
+        let longest = 7 + leading.max(trailing);
+        if shortest < self.min_length {
+            return Err(format!(
+                "UBW rules allow periods of {} days, shorter than the minimum of {}",
+                shortest, self.min_length
+            ));
+        }
+        if longest > self.max_length {
+            return Err(format!(
+                "UBW rules allow periods of {} days, longer than the maximum of {}",
+                longest, self.max_length
+            ));
+        }
+        Ok(())
+    }
+
+    /// Determines the boundaries of the UBW period containing a date
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - A date within the period
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the first and last dates of the period
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the calculation results in an invalid date
+    pub fn boundaries(&self, date: &NaiveDate) -> Result<(NaiveDate, NaiveDate), String> {
+        let (first, last) = month_boundaries(date)?;
+        let week = |date: NaiveDate| {
+            let monday = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+            (monday.max(first), (monday + Duration::days(6)).min(last))
+        };
+        let (week_start, week_end) = week(*date);
+        let (_, first_week_end) = week(first);
+        let (last_week_start, _) = week(last);
+
+        let leading_len = (first_week_end - first).num_days() as u32 + 1;
+        let trailing_len = (last - last_week_start).num_days() as u32 + 1;
+        let merge_leading = leading_len < 7 && leading_len <= self.leading_days();
+        let merge_trailing = trailing_len < 7 && trailing_len <= self.trailing_days();
+
+        //A short week at the end of the month starts with the week before it
+        let mut start = week_start;
+        if merge_trailing && week_start == last_week_start {
+            start = (last_week_start - Duration::days(7)).max(first);
+        }
+        if merge_leading && start == first_week_end + Duration::days(1) {
+            start = first;
+        }
+
+        //A short week at the start of the month ends with the week after it
+        let mut end = week_end;
+        if merge_leading && week_end == first_week_end {
+            end = (first_week_end + Duration::days(7)).min(last);
+        }
+        if merge_trailing && end + Duration::days(1) == last_week_start {
+            end = last;
+        }
+
+        Ok((start, end))
+    }
+
+    /// Returns the merge threshold of leading short weeks, 0 if they are not merged
+    fn leading_days(&self) -> u32 {
+        match self.direction {
+            MergeDirection::Both | MergeDirection::Forward => self.leading_merge_days,
+            MergeDirection::Backward | MergeDirection::None => 0,
+        }
+    }
+
+    /// Returns the merge threshold of trailing short weeks, 0 if they are not merged
+    fn trailing_days(&self) -> u32 {
+        match self.direction {
+            MergeDirection::Both | MergeDirection::Backward => self.trailing_merge_days,
+            MergeDirection::Forward | MergeDirection::None => 0,
+        }
+    }
+}
+
+/// How a date range is split into reporting periods
+///
+/// # Example
+///
+/// ```
+/// use time_tally::workhours::PeriodScheme;
+///
+/// let scheme: PeriodScheme = "month".parse().unwrap();
+/// assert_eq!(scheme, PeriodScheme::Month);
+/// assert_eq!("ubw".parse::<PeriodScheme>().unwrap(), PeriodScheme::default());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeriodScheme {
+    /// Weeks adjusted so that a month break never splits a period, as in Unit4 Business
+    /// World (UBW), with the given rules for short weeks
+    Ubw(UbwRules),
+    /// ISO weeks, split in two where a month break falls within the week
+    IsoWeek,
+    /// Calendar months
+    Month,
+    /// Two-week periods counted from an anchor date, e.g. the first day of a sprint, split
+    /// in two where a month break falls within the period. Periods can't be calculated
+    /// until `with_anchor` sets the anchor
+    BiWeekly(Option<NaiveDate>),
+    /// Fiscal years of 52 or 53 whole weeks starting on the Monday of the given ISO week,
+    /// with each quarter of 13 weeks split into periods of 4, 4 and 5 weeks. The 53rd week
+    /// of a long year is added to its last period. Periods are split where a month break
+    /// falls within them
+    Fiscal445(u32),
+}
+
+impl Default for PeriodScheme {
+    fn default() -> Self {
+        PeriodScheme::Ubw(UbwRules::default())
+    }
+}
+
+impl PeriodScheme {
+    /// Determines the boundaries of the period containing a date
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - A date within the period
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the first and last dates of the period
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the calculation results in an invalid date
+    pub fn boundaries(&self, date: &NaiveDate) -> Result<(NaiveDate, NaiveDate), String> {
+        match self {
+            PeriodScheme::Ubw(rules) => rules.boundaries(date),
+            PeriodScheme::IsoWeek => {
+                let monday = *date - Duration::days(date.weekday().num_days_from_monday() as i64);
+                split_at_month(date, monday, monday + Duration::days(6))
+            }
+            PeriodScheme::Month => month_boundaries(date),
+            PeriodScheme::BiWeekly(anchor) => {
+                let (start, end) = sprint(date, anchor)?;
+                split_at_month(date, start, end)
+            }
+            PeriodScheme::Fiscal445(start_week) => {
+                let (start, end, _) = fiscal_period(date, *start_week)?;
+                split_at_month(date, start, end)
+            }
+        }
+    }
+
+    /// Generates the name of a period
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The first date of the whole period
+    /// * `end` - The last date of the whole period
+    ///
+    /// # Returns
+    ///
+    /// A `String` such as "week: 23", "month: 6", "weeks: 23-24" or "period: 5". Bi-weekly
+    /// and 4-4-5 periods split at a month break are named after the period they were split from
+    pub fn name(&self, start: &NaiveDate, end: &NaiveDate) -> String {
+        match self {
+            PeriodScheme::Ubw(_) => period_name(start, end),
+            PeriodScheme::IsoWeek => format!("week: {}", start.iso_week().week()),
+            PeriodScheme::Month => format!("month: {}", start.month()),
+            PeriodScheme::BiWeekly(anchor) => {
+                //Both parts of a period split at a month break are named after the whole period
+                let (start, end) = sprint(start, anchor).unwrap_or((*start, *end));
+                format!(
+                    "weeks: {}-{}",
+                    start.iso_week().week(),
+                    end.iso_week().week()
+                )
+            }
+            PeriodScheme::Fiscal445(start_week) => match fiscal_period(start, *start_week) {
+                Ok((_, _, number)) => format!("period: {}", number),
+                Err(_) => format!("period: {}", start.format("%d-%m-%Y")),
+            },
+        }
+    }
+
+    /// Determines the position of a period among the periods starting in its month
+    ///
+    /// # Arguments
+    ///
+    /// * `period_start` - The first date of the whole period
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the sequence number of the period, starting at 1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the calculation results in an invalid date
+    pub fn sequence_in_month(&self, period_start: &NaiveDate) -> Result<u32, String> {
+        let (first, _) = month_boundaries(period_start)?;
+        let (mut start, mut end) = self.boundaries(&first)?;
+        //A period containing the first of the month may start in the month before
+        if start < first {
+            (start, end) = self.boundaries(&(end + Duration::days(1)))?;
+        }
+
+        let mut sequence = 1;
+        while start < *period_start {
+            (start, end) = self.boundaries(&(end + Duration::days(1)))?;
+            sequence += 1;
+        }
+        Ok(sequence)
+    }
+
+    /// Sets the anchor date of a bi-weekly scheme
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the scheme is not bi-weekly
+    pub fn with_anchor(self, anchor: NaiveDate) -> Result<Self, String> {
+        match self {
+            PeriodScheme::BiWeekly(_) => Ok(PeriodScheme::BiWeekly(Some(anchor))),
+            _ => Err("An anchor date is only supported for the biweekly scheme".to_string()),
+        }
+    }
+
+    /// Sets the ISO week, from 1 to 52, that the fiscal years of a 4-4-5 scheme start in
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the scheme is not 4-4-5 or the week is not between 1 and 52
+    pub fn with_fiscal_start_week(self, week: u32) -> Result<Self, String> {
+        match self {
+            PeriodScheme::Fiscal445(_) if (1..=52).contains(&week) => {
+                Ok(PeriodScheme::Fiscal445(week))
+            }
+            PeriodScheme::Fiscal445(_) => Err(format!(
+                "Invalid fiscal start week {}: expected a week between 1 and 52",
+                week
+            )),
+            _ => Err("A fiscal start week is only supported for the fiscal445 scheme".to_string()),
+        }
+    }
+
+    /// Sets the rules of a UBW scheme, leaving other schemes unchanged
+    pub fn with_ubw_rules(self, rules: UbwRules) -> Self {
+        match self {
+            PeriodScheme::Ubw(_) => PeriodScheme::Ubw(rules),
+            scheme => scheme,
+        }
+    }
+}
+
+/// Parses a scheme from its name
+///
+/// A bi-weekly scheme has no anchor until `with_anchor` sets one, as no default fits every
+/// sprint cadence, and a 4-4-5 scheme starts its fiscal years in ISO week 1 until
+/// `with_fiscal_start_week` says otherwise.
+impl FromStr for PeriodScheme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ubw" => Ok(PeriodScheme::default()),
+            "isoweek" | "iso_week" => Ok(PeriodScheme::IsoWeek),
+            "month" => Ok(PeriodScheme::Month),
+            "biweekly" | "bi_weekly" => Ok(PeriodScheme::BiWeekly(None)),
+            "fiscal445" | "4-4-5" => Ok(PeriodScheme::Fiscal445(DEFAULT_FISCAL_START_WEEK)),
+            _ => Err(format!(
+                "Invalid scheme '{}': expected ubw, isoweek, month, biweekly or fiscal445",
+                s
+            )),
+        }
+    }
+}
+
+/// Returns the first and last dates of the month of a date
+fn month_boundaries(date: &NaiveDate) -> Result<(NaiveDate, NaiveDate), String> {
+    let first = date.with_day(1).ok_or("Invalid start date")?;
+    let last = date
+        .with_day(date.days_in_month() as u32)
+        .ok_or("Invalid end date")?;
+    Ok((first, last))
+}
+
+/// Returns the first and last dates of the two-week period of a date, before it is split at
+/// a month break
+///
+/// # Errors
+///
+/// Returns an error if the scheme has no anchor
+fn sprint(date: &NaiveDate, anchor: &Option<NaiveDate>) -> Result<(NaiveDate, NaiveDate), String> {
+    let anchor = anchor.ok_or("The biweekly scheme requires an anchor")?;
+    let offset = (*date - anchor).num_days().div_euclid(14) * 14;
+    let start = anchor + Duration::days(offset);
+    Ok((start, start + Duration::days(13)))
+}
+
+/// Returns the part of a period from `start` to `end` within the month of a date
+fn split_at_month(
+    date: &NaiveDate,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<(NaiveDate, NaiveDate), String> {
+    let (first, last) = month_boundaries(date)?;
+    Ok((start.max(first), end.min(last)))
+}
+
+/// Returns the first day of a 4-4-5 fiscal year, the Monday of its start week
+fn fiscal_year_start(year: i32, start_week: u32) -> Result<NaiveDate, String> {
+    NaiveDate::from_isoywd_opt(year, start_week, Weekday::Mon).ok_or_else(|| {
+        format!(
+            "Invalid fiscal year {} starting in week {}",
+            year, start_week
+        )
+    })
+}
+
+/// Returns the first and last dates and the number (1 to 12) of the 4-4-5 period of a date
+///
+/// # Arguments
+///
+/// * `date` - A date within the period
+/// * `start_week` - The ISO week the fiscal years start in
+fn fiscal_period(date: &NaiveDate, start_week: u32) -> Result<(NaiveDate, NaiveDate, u32), String> {
+    let mut year = date.iso_week().year();
+    let mut year_start = fiscal_year_start(year, start_week)?;
+    if *date < year_start {
+        year -= 1;
+        year_start = fiscal_year_start(year, start_week)?;
+    }
+    let next_year_start = fiscal_year_start(year + 1, start_week)?;
+
+    let week = (*date - year_start).num_days() / 7;
+    let quarter = (week / 13).min(3);
+    let index = match week - quarter * 13 {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    };
+    let number = quarter * 3 + index + 1;
+    let start = year_start + Duration::weeks(quarter * 13 + [0, 4, 8][index as usize]);
+    let end = if number == 12 {
+        next_year_start - Duration::days(1)
+    } else {
+        start + Duration::weeks([4, 4, 5][index as usize]) - Duration::days(1)
+    };
+    Ok((start, end, number as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    //april 2024: mon 1 - tue 30, october 2024: tue 1 - thu 31
+    #[test]
+    fn ubw_rules_change_merges() {
+        let no_trailing = UbwRules {
+            direction: MergeDirection::Forward,
+            ..Default::default()
+        };
+        assert_eq!(
+            no_trailing.boundaries(&date(2024, 4, 30)).unwrap(),
+            (date(2024, 4, 29), date(2024, 4, 30))
+        );
+        assert_eq!(
+            UbwRules::default().boundaries(&date(2024, 4, 30)).unwrap(),
+            (date(2024, 4, 22), date(2024, 4, 30))
+        );
+
+        let leading_six = UbwRules {
+            leading_merge_days: 6,
+            max_length: 13,
+            ..Default::default()
+        };
+        leading_six.validate().unwrap();
+        assert_eq!(
+            leading_six.boundaries(&date(2024, 10, 1)).unwrap(),
+            (date(2024, 10, 1), date(2024, 10, 13))
+        );
+        assert_eq!(
+            UbwRules::default().boundaries(&date(2024, 10, 1)).unwrap(),
+            (date(2024, 10, 1), date(2024, 10, 6))
+        );
+    }
+
+    #[test]
+    fn ubw_rules_are_validated() {
+        UbwRules::default().validate().unwrap();
+        let invalid = [
+            UbwRules {
+                leading_merge_days: 6,
+                ..Default::default()
+            },
+            UbwRules {
+                trailing_merge_days: 0,
+                min_length: 3,
+                direction: MergeDirection::None,
+                ..Default::default()
+            },
+            UbwRules {
+                trailing_merge_days: 7,
+                max_length: 14,
+                ..Default::default()
+            },
+        ];
+        for rules in invalid {
+            assert!(rules.validate().is_err(), "{:?}", rules);
+        }
+    }
+
+    #[test]
+    fn iso_weeks_split_at_month_breaks() {
+        let scheme = PeriodScheme::IsoWeek;
+        assert_eq!(
+            scheme.boundaries(&date(2024, 5, 29)).unwrap(),
+            (date(2024, 5, 27), date(2024, 5, 31))
+        );
+        assert_eq!(
+            scheme.boundaries(&date(2024, 6, 1)).unwrap(),
+            (date(2024, 6, 1), date(2024, 6, 2))
+        );
+        assert_eq!(
+            scheme.name(&date(2024, 6, 1), &date(2024, 6, 2)),
+            "week: 22"
+        );
+    }
+
+    #[test]
+    fn sequences_within_months() {
+        let ubw = PeriodScheme::default();
+        assert_eq!(ubw.sequence_in_month(&date(2024, 12, 1)).unwrap(), 1);
+        assert_eq!(ubw.sequence_in_month(&date(2024, 12, 30)).unwrap(), 5);
+
+        //29 jan-11 feb 2024 is split into 29-31 jan and 1-11 feb
+        let bi_weekly = PeriodScheme::BiWeekly(Some(date(2024, 1, 1)));
+        assert_eq!(bi_weekly.sequence_in_month(&date(2024, 1, 29)).unwrap(), 3);
+        assert_eq!(bi_weekly.sequence_in_month(&date(2024, 2, 1)).unwrap(), 1);
+        assert_eq!(bi_weekly.sequence_in_month(&date(2024, 2, 12)).unwrap(), 2);
+    }
+
+    #[test]
+    fn months() {
+        assert_eq!(
+            PeriodScheme::Month.boundaries(&date(2024, 2, 14)).unwrap(),
+            (date(2024, 2, 1), date(2024, 2, 29))
+        );
+    }
+
+    #[test]
+    fn bi_weekly_from_anchor() {
+        let unanchored = "biweekly".parse::<PeriodScheme>().unwrap();
+        assert_eq!(unanchored, PeriodScheme::BiWeekly(None));
+        assert!(unanchored.boundaries(&date(2024, 1, 8)).is_err());
+
+        let scheme = unanchored.with_anchor(date(2024, 1, 8)).unwrap();
+        assert_eq!(
+            scheme.boundaries(&date(2024, 1, 21)).unwrap(),
+            (date(2024, 1, 8), date(2024, 1, 21))
+        );
+        assert_eq!(
+            scheme.boundaries(&date(2023, 12, 28)).unwrap(),
+            (date(2023, 12, 25), date(2023, 12, 31))
+        );
+        assert_eq!(
+            scheme.boundaries(&date(2024, 1, 5)).unwrap(),
+            (date(2024, 1, 1), date(2024, 1, 7))
+        );
+        assert_eq!(
+            scheme.name(&date(2024, 1, 8), &date(2024, 1, 21)),
+            "weeks: 2-3"
+        );
+
+        //1-4 feb 2024 is a single week of the period of 22 jan-4 feb, split at the month break
+        assert_eq!(
+            scheme.boundaries(&date(2024, 2, 2)).unwrap(),
+            (date(2024, 2, 1), date(2024, 2, 4))
+        );
+        assert_eq!(
+            scheme.name(&date(2024, 2, 1), &date(2024, 2, 4)),
+            "weeks: 4-5"
+        );
+        assert_eq!(
+            scheme.name(&date(2024, 1, 22), &date(2024, 1, 31)),
+            "weeks: 4-5"
+        );
+        assert!(PeriodScheme::Month.with_anchor(date(2024, 1, 8)).is_err());
+    }
+
+    //fiscal 2024 from week 1: mon 1 jan 2024 to sun 29 dec 2024
+    #[test]
+    fn fiscal_periods_are_4_4_5_weeks() {
+        let scheme = PeriodScheme::Fiscal445(1);
+        assert_eq!(
+            scheme.boundaries(&date(2024, 1, 28)).unwrap(),
+            (date(2024, 1, 1), date(2024, 1, 28))
+        );
+        assert_eq!(
+            scheme.boundaries(&date(2024, 2, 26)).unwrap(),
+            (date(2024, 2, 26), date(2024, 2, 29))
+        );
+        assert_eq!(
+            scheme.boundaries(&date(2024, 3, 31)).unwrap(),
+            (date(2024, 3, 1), date(2024, 3, 31))
+        );
+        assert_eq!(
+            scheme.boundaries(&date(2024, 4, 1)).unwrap(),
+            (date(2024, 4, 1), date(2024, 4, 28))
+        );
+        assert_eq!(
+            scheme.boundaries(&date(2024, 11, 25)).unwrap(),
+            (date(2024, 11, 25), date(2024, 11, 30))
+        );
+        assert_eq!(
+            scheme.boundaries(&date(2024, 12, 29)).unwrap(),
+            (date(2024, 12, 1), date(2024, 12, 29))
+        );
+        assert_eq!(
+            scheme.name(&date(2024, 11, 25), &date(2024, 12, 29)),
+            "period: 12"
+        );
+        assert_eq!(
+            scheme.boundaries(&date(2024, 12, 30)).unwrap(),
+            (date(2024, 12, 30), date(2024, 12, 31))
+        );
+        assert_eq!(
+            scheme.name(&date(2024, 12, 30), &date(2024, 12, 31)),
+            "period: 1"
+        );
+    }
+
+    //2026 has 53 ISO weeks, so the last period of fiscal 2026 has 6 weeks, 23 nov 2026 to 3 jan 2027
+    #[test]
+    fn long_fiscal_years_extend_the_last_period() {
+        let scheme = PeriodScheme::Fiscal445(1);
+        assert_eq!(
+            scheme.boundaries(&date(2026, 11, 23)).unwrap(),
+            (date(2026, 11, 23), date(2026, 11, 30))
+        );
+        assert_eq!(
+            scheme.boundaries(&date(2027, 1, 3)).unwrap(),
+            (date(2027, 1, 1), date(2027, 1, 3))
+        );
+        assert_eq!(
+            scheme.name(&date(2027, 1, 1), &date(2027, 1, 3)),
+            "period: 12"
+        );
+    }
+
+    //fiscal years starting in week 27: fiscal 2024 from mon 1 jul 2024
+    #[test]
+    fn fiscal_years_start_in_the_given_week() {
+        let scheme = "fiscal445"
+            .parse::<PeriodScheme>()
+            .unwrap()
+            .with_fiscal_start_week(27)
+            .unwrap();
+        assert_eq!(
+            scheme.boundaries(&date(2024, 7, 10)).unwrap(),
+            (date(2024, 7, 1), date(2024, 7, 28))
+        );
+        assert_eq!(
+            scheme.boundaries(&date(2024, 6, 30)).unwrap(),
+            (date(2024, 6, 1), date(2024, 6, 30))
+        );
+        assert_eq!(
+            scheme.name(&date(2024, 5, 27), &date(2024, 6, 30)),
+            "period: 12"
+        );
+        assert!(scheme.with_fiscal_start_week(53).is_err());
+        assert!(PeriodScheme::Month.with_fiscal_start_week(1).is_err());
+    }
+}
@@ -0,0 +1,496 @@
+//! # Vacation
+//!
+//! This module accounts for vacation days under the Swedish Annual Leave Act
+//! ("semesterlagen", SFS 1977:480):
+//!
+//! - Paid vacation days are earned during the earning year ("intjänandeår"), the vacation
+//!   year before the one they are taken in. A full earning year gives 25 paid days, and a
+//!   part of one a share of them, rounded up to whole days.
+//! - Absence does not reduce the days earned up to a limit per earning year: 180 days of
+//!   sick leave and 120 days each of parental leave and care of a sick child ("VAB").
+//! - Paid days beyond 20 that are not taken may be saved to later years.
+//! - A vacation consumes the vacation days ("semesterdagar") it covers: every weekday except
+//!   public holidays, midsommarafton, julafton and nyårsafton. Vacation days are counted the
+//!   same way whatever the work schedule and employment rate, which only decide the work
+//!   hours a vacation covers, so a week off uses five days on a four-day schedule too.
+
+use super::holidays::{Holiday, HolidayKind};
+use super::{
+    parse_dates, scheduled_hours, Absence, AbsenceKind, CalculationOptions, Hours, WorkhoursError,
+};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::Serialize;
+
+/// The paid vacation days of a full earning year by law
+pub const STATUTORY_PAID_DAYS: u32 = 25;
+
+/// The paid vacation days that must be taken each year, the rest may be saved
+pub const MIN_DAYS_TAKEN: u32 = 20;
+
+/// The days of each type of absence per earning year that still earn vacation
+fn qualifying_limit(kind: AbsenceKind) -> Option<i64> {
+    match kind {
+        AbsenceKind::Vacation => None,
+        AbsenceKind::SickLeave => Some(180),
+        AbsenceKind::Vab | AbsenceKind::ParentalLeave => Some(120),
+    }
+}
+
+/// How vacation is earned and when the vacation year starts
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use time_tally::workhours::VacationRules;
+///
+/// let rules = VacationRules::default();
+/// let (start, end) = rules.vacation_year(2024).unwrap();
+/// assert_eq!(start, NaiveDate::from_ymd_opt(2024, 4, 1).unwrap());
+/// assert_eq!(end, NaiveDate::from_ymd_opt(2025, 3, 31).unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VacationRules {
+    /// The paid days of a full earning year, 25 by law but often more by collective agreement
+    pub paid_days: u32,
+    /// The month the vacation year starts in, 4 (April) by law
+    pub start_month: u32,
+}
+
+impl Default for VacationRules {
+    fn default() -> Self {
+        VacationRules {
+            paid_days: STATUTORY_PAID_DAYS,
+            start_month: 4,
+        }
+    }
+}
+
+impl VacationRules {
+    /// Creates vacation rules
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the start month is not between 1 and 12 or the paid days are
+    /// fewer than the statutory 25 days
+    pub fn new(paid_days: u32, start_month: u32) -> Result<Self, String> {
+        if !(1..=12).contains(&start_month) {
+            return Err(format!(
+                "Invalid vacation year start month {}: must be between 1 and 12",
+                start_month
+            ));
+        }
+        if paid_days < STATUTORY_PAID_DAYS {
+            return Err(format!(
+                "Invalid paid vacation days {}: must be at least {}",
+                paid_days, STATUTORY_PAID_DAYS
+            ));
+        }
+        Ok(VacationRules {
+            paid_days,
+            start_month,
+        })
+    }
+
+    /// Returns the first and last days of the vacation year starting in a year
+    pub fn vacation_year(&self, year: i32) -> Option<(NaiveDate, NaiveDate)> {
+        let start = NaiveDate::from_ymd_opt(year, self.start_month, 1)?;
+        let next = NaiveDate::from_ymd_opt(year.checked_add(1)?, self.start_month, 1)?;
+        Some((start, next.pred_opt()?))
+    }
+
+    /// Returns the first and last days of the earning year of the vacation year starting in
+    /// a year
+    pub fn earning_year(&self, year: i32) -> Option<(NaiveDate, NaiveDate)> {
+        self.vacation_year(year.checked_sub(1)?)
+    }
+}
+
+/// The vacation days earned for, taken in and left of a vacation year
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct VacationBalance {
+    /// The first day of the vacation year
+    pub vacation_year_start: NaiveDate,
+    /// The last day of the vacation year
+    pub vacation_year_end: NaiveDate,
+    /// The first day of the earning year
+    pub earning_year_start: NaiveDate,
+    /// The last day of the earning year
+    pub earning_year_end: NaiveDate,
+    /// The days of the earning year that earned vacation ("semesterlönegrundande dagar")
+    pub qualifying_days: i64,
+    /// The paid vacation days earned
+    pub paid_days: u32,
+    /// The vacation days that may be taken without pay, making up for days not earned
+    pub unpaid_days: u32,
+    /// The paid vacation days saved from earlier years ("sparade dagar")
+    pub saved_days: u32,
+    /// The vacation days of whole-day vacation absences within the vacation year, drawn
+    /// from the paid days of the year before the saved days
+    pub used_days: u32,
+    /// The paid and saved vacation days not yet used
+    pub remaining_days: u32,
+    /// The remaining paid days of the year that may be saved to later years, at most the
+    /// paid days beyond the 20 that must be taken
+    pub saveable_days: u32,
+    /// The vacation days a requested vacation consumes, if one was given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requested: Option<RequestedVacation>,
+}
+
+/// The vacation days a requested vacation consumes
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct RequestedVacation {
+    /// The first day of the vacation
+    pub start: NaiveDate,
+    /// The last day of the vacation
+    pub end: NaiveDate,
+    /// The vacation days within the vacation, each consuming a paid or saved day
+    pub days: u32,
+    /// The work hours within the vacation
+    pub hours: Hours,
+    /// Whether the remaining paid and saved days cover the vacation
+    pub covered: bool,
+}
+
+/// Calculates the vacation balance of a vacation year
+///
+/// # Arguments
+///
+/// * `year` - The year the vacation year starts in
+/// * `employment_start` - The first day of employment, if within or after the earning year
+/// * `saved_days` - The paid vacation days saved from earlier years and not yet taken
+/// * `requested` - The start and end dates of a requested vacation, in any of the formats
+///   accepted by `calculate_workhours`, to count the vacation days of
+/// * `rules` - How vacation is earned and when the vacation year starts
+/// * `options` - The holiday calendar deciding which days are vacation days, the work
+///   schedule and employment rate deciding the hours of a requested vacation, and the
+///   absences during the earning and vacation years
+///
+/// # Returns
+///
+/// A `Result` containing the vacation balance, or a `WorkhoursError` if not
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The year is out of range
+/// - The dates of the requested vacation are not in any of the accepted formats, or are
+///   ambiguous or reversed
+/// - There's an issue calculating holidays
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use time_tally::workhours::{vacation_balance, CalculationOptions, VacationRules};
+///
+/// //Employed from 1 October 2023, half way through the earning year
+/// let balance = vacation_balance(
+///     2024,
+///     NaiveDate::from_ymd_opt(2023, 10, 1),
+///     0,
+///     Some(("2024-07-01".to_string(), "2024-07-12".to_string())),
+///     &VacationRules::default(),
+///     &CalculationOptions::default(),
+/// )
+/// .unwrap();
+/// assert_eq!(balance.paid_days, 13);
+/// assert_eq!(balance.unpaid_days, 12);
+/// assert_eq!(balance.requested.unwrap().days, 10);
+/// ```
+pub fn vacation_balance(
+    year: i32,
+    employment_start: Option<NaiveDate>,
+    saved_days: u32,
+    requested: Option<(String, String)>,
+    rules: &VacationRules,
+    options: &CalculationOptions,
+) -> Result<VacationBalance, WorkhoursError> {
+    let invalid_year = || WorkhoursError::InvalidYear(year);
+    let (vacation_start, vacation_end) = rules.vacation_year(year).ok_or_else(invalid_year)?;
+    let (earning_start, earning_end) = rules.earning_year(year).ok_or_else(invalid_year)?;
+
+    //Days of the earning year before the employment started earn nothing
+    let employed_from = employment_start.map_or(earning_start, |start| start.max(earning_start));
+    let mut qualifying_days = days_between(employed_from, earning_end);
+    for kind in [
+        AbsenceKind::SickLeave,
+        AbsenceKind::Vab,
+        AbsenceKind::ParentalLeave,
+    ] {
+        let Some(limit) = qualifying_limit(kind) else {
+            continue;
+        };
+        let absent = whole_days(&options.absences, kind, employed_from, earning_end).count();
+        qualifying_days -= (absent as i64 - limit).max(0);
+    }
+    let qualifying_days = qualifying_days.max(0);
+    let earning_days = days_between(earning_start, earning_end);
+    let paid_days = (rules.paid_days as i64 * qualifying_days + earning_days - 1) / earning_days;
+    let paid_days = paid_days as u32;
+
+    let used_days = vacation_days(
+        whole_days(
+            &options.absences,
+            AbsenceKind::Vacation,
+            vacation_start,
+            vacation_end,
+        ),
+        options,
+    )?
+    .0;
+    //Days are used from the paid days of the year first, so that the rest can be saved
+    let remaining_paid_days = paid_days.saturating_sub(used_days);
+    let remaining_saved_days = saved_days.saturating_sub(used_days.saturating_sub(paid_days));
+    let remaining_days = remaining_paid_days.saturating_add(remaining_saved_days);
+
+    let requested = requested
+        .map(|(start, end)| {
+            let (start, end) = parse_dates(start, end)?;
+            let (days, hours) = vacation_days(dates(start, end), options)?;
+            Ok::<_, WorkhoursError>(RequestedVacation {
+                start,
+                end,
+                days,
+                hours,
+                covered: days <= remaining_days,
+            })
+        })
+        .transpose()?;
+
+    Ok(VacationBalance {
+        vacation_year_start: vacation_start,
+        vacation_year_end: vacation_end,
+        earning_year_start: earning_start,
+        earning_year_end: earning_end,
+        qualifying_days,
+        paid_days,
+        unpaid_days: rules.paid_days.saturating_sub(paid_days),
+        saved_days,
+        used_days,
+        remaining_days,
+        saveable_days: remaining_paid_days.min(paid_days.saturating_sub(MIN_DAYS_TAKEN)),
+        requested,
+    })
+}
+
+/// Returns the number of days between two dates (inclusive), or 0 if they are reversed
+fn days_between(start: NaiveDate, end: NaiveDate) -> i64 {
+    ((end - start).num_days() + 1).max(0)
+}
+
+/// Returns the days between two dates (inclusive)
+fn dates(start: NaiveDate, end: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+    (0..days_between(start, end)).map(move |offset| start + Duration::days(offset))
+}
+
+/// Returns the days between two dates (inclusive) covered by whole-day absences of a type
+fn whole_days(
+    absences: &[Absence],
+    kind: AbsenceKind,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> impl Iterator<Item = NaiveDate> + '_ {
+    dates(start, end).filter(move |date| {
+        absences.iter().any(|absence| {
+            absence.kind == kind
+                && absence.hours.is_none()
+                && absence.from <= *date
+                && *date <= absence.to
+        })
+    })
+}
+
+/// Checks whether a day is a vacation day: a weekday that is not a public holiday or a
+/// customary day off, i.e. midsommarafton, julafton or nyårsafton in the Swedish calendar
+fn is_vacation_day(date: NaiveDate, holidays: &[Holiday]) -> bool {
+    !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+        && !holidays.iter().any(|holiday| {
+            holiday.date == date
+                && matches!(
+                    holiday.kind,
+                    HolidayKind::PublicHoliday | HolidayKind::CustomaryDayOff
+                )
+        })
+}
+
+/// Counts the vacation days among some days and sums the work hours of all of them
+fn vacation_days(
+    days: impl Iterator<Item = NaiveDate>,
+    options: &CalculationOptions,
+) -> Result<(u32, Hours), WorkhoursError> {
+    let days: Vec<NaiveDate> = days.collect();
+    let (Some(first), Some(last)) = (days.first(), days.last()) else {
+        return Ok((0, Hours::ZERO));
+    };
+    let holidays = options
+        .calendar
+        .for_years(first.year(), last.year())
+        .map_err(WorkhoursError::Calendar)?;
+
+    let mut count = 0;
+    let mut hours = Hours::ZERO;
+    for date in days {
+        if is_vacation_day(date, &holidays) {
+            count += 1;
+        }
+        hours += scheduled_hours(date, &holidays, options);
+    }
+    Ok((count, hours))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workhours::{EmploymentRate, WorkSchedule};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn options(absences: &[&str]) -> CalculationOptions {
+        CalculationOptions {
+            absences: absences
+                .iter()
+                .map(|absence| absence.parse().unwrap())
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn full_earning_year() {
+        let balance = vacation_balance(
+            2024,
+            None,
+            0,
+            None,
+            &VacationRules::default(),
+            &options(&[]),
+        )
+        .unwrap();
+        assert_eq!(balance.earning_year_start, date(2023, 4, 1));
+        assert_eq!(balance.earning_year_end, date(2024, 3, 31));
+        assert_eq!(balance.qualifying_days, 366);
+        assert_eq!(balance.paid_days, 25);
+        assert_eq!(balance.unpaid_days, 0);
+        assert_eq!(balance.saveable_days, 5);
+    }
+
+    #[test]
+    fn absence_beyond_the_limits_reduces_paid_days() {
+        //200 days of sick leave, 20 beyond the limit, and 100 days of parental leave
+        let absences = [
+            "2023-04-01..2023-10-17:sick_leave",
+            "2023-11-01..2024-02-08:parental_leave",
+            "2023-10-30:sick_leave:4",
+        ];
+        let balance = vacation_balance(
+            2024,
+            None,
+            0,
+            None,
+            &VacationRules::default(),
+            &options(&absences),
+        )
+        .unwrap();
+        assert_eq!(balance.qualifying_days, 366 - 20);
+        //25 * 346 / 366 = 23.6, rounded up
+        assert_eq!(balance.paid_days, 24);
+        assert_eq!(balance.unpaid_days, 1);
+    }
+
+    #[test]
+    fn used_and_requested_days() {
+        //Midsommarafton on fri 21 jun 2024 is not a working day
+        let absences = [
+            "2024-06-17..2024-06-28:vacation",
+            "2024-08-02:vacation:4",
+            "2023-07-03..2023-07-07:vacation",
+        ];
+        let balance = vacation_balance(
+            2024,
+            None,
+            0,
+            Some(("2024-07-01".to_string(), "2024-07-28".to_string())),
+            &VacationRules::default(),
+            &options(&absences),
+        )
+        .unwrap();
+        assert_eq!(balance.used_days, 9);
+        assert_eq!(balance.remaining_days, 16);
+        assert_eq!(balance.saveable_days, 5);
+
+        let requested = balance.requested.unwrap();
+        assert_eq!(requested.days, 20);
+        assert_eq!(requested.hours, Hours::from_hours(8 * 20));
+        assert!(!requested.covered);
+    }
+
+    #[test]
+    fn days_do_not_depend_on_the_schedule() {
+        //Four days a week at 80%: a week off still uses five vacation days
+        let options = |absences: &[&str]| CalculationOptions {
+            schedule: "8,8,8,8,0,0,0".parse::<WorkSchedule>().unwrap().into(),
+            employment_rate: EmploymentRate::new(80.0).unwrap(),
+            ..options(absences)
+        };
+        let absences = [
+            "2024-06-03..2024-06-30:vacation",
+            "2024-08-05..2024-08-09:vacation",
+        ];
+        let balance = vacation_balance(
+            2024,
+            None,
+            3,
+            Some(("2024-12-23".to_string(), "2024-12-27".to_string())),
+            &VacationRules::default(),
+            &options(&absences),
+        )
+        .unwrap();
+        //June has 20 weekdays, less nationaldagen and midsommarafton
+        assert_eq!(balance.used_days, 18 + 5);
+        assert_eq!(balance.saved_days, 3);
+        assert_eq!(balance.remaining_days, 2 + 3);
+        assert_eq!(balance.saveable_days, 2);
+
+        //Julafton and the two days of Christmas are not vacation days, fri 27 dec is one
+        //but has no work hours
+        let requested = balance.requested.unwrap();
+        assert_eq!(requested.days, 2);
+        assert_eq!(requested.hours, Hours::from_minutes(384));
+        assert!(requested.covered);
+    }
+
+    #[test]
+    fn saved_days_are_used_after_the_paid_days() {
+        let absences = [
+            "2024-06-03..2024-06-30:vacation",
+            "2024-08-05..2024-08-16:vacation",
+        ];
+        let balance = vacation_balance(
+            2024,
+            None,
+            3,
+            None,
+            &VacationRules::default(),
+            &options(&absences),
+        )
+        .unwrap();
+        assert_eq!(balance.used_days, 28);
+        assert_eq!(balance.remaining_days, 0);
+        assert_eq!(balance.saveable_days, 0);
+    }
+
+    #[test]
+    fn rules_are_validated() {
+        assert!(VacationRules::new(25, 13).is_err());
+        assert!(VacationRules::new(20, 4).is_err());
+        let rules = VacationRules::new(30, 1).unwrap();
+        assert_eq!(
+            rules.earning_year(2024),
+            Some((date(2023, 1, 1), date(2023, 12, 31)))
+        );
+    }
+}